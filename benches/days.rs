@@ -0,0 +1,20 @@
+//! `cargo bench` runs `aoc bench --all`; pass days or options after `--`,
+//! e.g. `cargo bench --bench days -- 11 14..=15 --iterations 3 --json`.
+
+use std::{env, process::ExitCode};
+
+fn main() -> ExitCode {
+    // cargo passes --bench to every bench target
+    let mut args = env::args()
+        .skip(1)
+        .filter(|a| a != "--bench")
+        .collect::<Vec<_>>();
+    let selected = args.iter().enumerate().any(|(i, a)| {
+        let value = i > 0 && matches!(args[i - 1].as_str(), "--iterations" | "--input");
+        !value && (a == "--all" || aoc::runner::parse_selection(a).is_ok())
+    });
+    if !selected {
+        args.push("--all".to_string());
+    }
+    aoc::bench::main(&args)
+}
@@ -0,0 +1,214 @@
+use std::{collections::HashMap, process::ExitCode};
+
+use toml::{Table, Value};
+
+use crate::{
+    error::{AocError, Result},
+    input, runner,
+};
+
+pub const PATH: &str = "answers.toml";
+
+pub const USAGE: &str = "usage: aoc verify [<day>|<from>..<to>|<from>..=<to>|--all]";
+
+/// The known answers to the real puzzle inputs, keyed by day and part.
+/// `answers.toml` has a table per day:
+///
+/// ```toml
+/// [day01]
+/// part1 = 71124
+/// part2 = 204639
+/// ```
+///
+/// Answers may be integers or strings; multi-line answers (day 10's screen)
+/// use `'''` strings.
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(u32, u32), String>);
+
+impl Answers {
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.0.get(&(day, part)).map(|s| s.as_str())
+    }
+}
+
+/// Parse TOML, reporting errors against the offending line.
+pub(crate) fn parse_toml(text: &str) -> Result<Table> {
+    text.parse::<Table>().map_err(|e| {
+        let line = e
+            .span()
+            .map(|s| text[..s.start].matches('\n').count() + 1)
+            .unwrap_or(0);
+        let found = text.lines().nth(line.max(1) - 1).unwrap_or("");
+        let expected = match e.message().trim() {
+            "" => "valid TOML".to_string(),
+            message => format!("valid TOML ({})", message.replace('\n', "; ")),
+        };
+        AocError::parse(found, &expected).at_line(line)
+    })
+}
+
+/// The day number and contents of a `[dayNN]` table.
+pub(crate) fn day_table<'a>(key: &str, value: &'a Value) -> Result<(u32, &'a Table)> {
+    let error = || AocError::parse(key, "a `[dayNN]` table");
+    let day = key
+        .strip_prefix("day")
+        .and_then(|d| d.parse::<u32>().ok())
+        .ok_or_else(error)?;
+    Ok((day, value.as_table().ok_or_else(error)?))
+}
+
+/// The `part1` and `part2` answers in a table, either of which may be missing.
+pub(crate) fn parts(table: &Table) -> Result<Vec<(u32, String)>> {
+    table
+        .iter()
+        .map(|(key, answer)| {
+            let part = match key.as_str() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(AocError::parse(key, "`part1` or `part2`")),
+            };
+            let answer = match answer {
+                Value::String(s) => input::normalise(s),
+                Value::Integer(n) => n.to_string(),
+                _ => return Err(AocError::parse(&answer.to_string(), "an integer or string")),
+            };
+            Ok((part, answer))
+        })
+        .collect()
+}
+
+pub fn parse(text: &str) -> Result<Answers> {
+    let mut answers = HashMap::new();
+    for (key, value) in &parse_toml(text)? {
+        let (day, table) = day_table(key, value)?;
+        for (part, answer) in parts(table)? {
+            answers.insert((day, part), answer);
+        }
+    }
+    Ok(Answers(answers))
+}
+
+pub fn load(path: &str) -> Result<Answers> {
+    input::load(path).and_then(|text| parse(&text).map_err(|e| e.in_file(path)))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// No answer is recorded for this part.
+    Missing,
+    Error(String),
+}
+
+pub fn check(expected: Option<&str>, actual: &std::result::Result<String, String>) -> Verdict {
+    match (expected, actual) {
+        (_, Err(e)) => Verdict::Error(e.clone()),
+        (None, Ok(_)) => Verdict::Missing,
+        (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass,
+        (Some(expected), Ok(actual)) => Verdict::Fail {
+            expected: expected.to_string(),
+            actual: actual.clone(),
+        },
+    }
+}
+
+pub fn describe(v: &Verdict) -> String {
+    // Keep multi-line answers on one row
+    let flat = |s: &str| s.replace('\n', "\\n");
+    match v {
+        Verdict::Pass => "pass".to_string(),
+        Verdict::Fail { expected, actual } => {
+            format!("FAIL: expected {}, got {}", flat(expected), flat(actual))
+        }
+        Verdict::Missing => "missing".to_string(),
+        Verdict::Error(e) => format!("ERROR: {}", e),
+    }
+}
+
+/// The `aoc verify` command: run each selected day, every day by default,
+/// against its data file and compare with `answers.toml`. Missing answers
+/// are reported but don't fail the run.
+pub fn main(args: &[String]) -> ExitCode {
+    let all = ["--all".to_string()];
+    let args = if args.is_empty() { &all[..] } else { args };
+    let days = match runner::select(args, None) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let answers = match load(PATH) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // pass, fail, missing, error
+    let mut counts = [0; 4];
+    println!("Day | Part | Result");
+    println!("----+------+-------");
+    for (day, source) in days {
+        let outcome = runner::run(day.as_ref(), source.as_deref());
+        for (part, actual) in [(1, &outcome.part1), (2, &outcome.part2)] {
+            let verdict = check(answers.get(outcome.day, part), actual);
+            counts[match verdict {
+                Verdict::Pass => 0,
+                Verdict::Fail { .. } => 1,
+                Verdict::Missing => 2,
+                Verdict::Error(_) => 3,
+            }] += 1;
+            println!("{:>3} | {:>4} | {}", outcome.day, part, describe(&verdict));
+        }
+    }
+    println!(
+        "\n{} passed, {} failed, {} missing, {} errors",
+        counts[0], counts[1], counts[2], counts[3]
+    );
+    if counts[1] + counts[3] > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let answers = parse("[day05]\npart1 = \"CMZ\"\n\n[day11]\npart2 = 2713310158\n").unwrap();
+        assert_eq!(answers.get(5, 1), Some("CMZ"));
+        assert_eq!(answers.get(5, 2), None);
+        assert_eq!(answers.get(11, 2), Some("2713310158"));
+        assert_eq!(
+            parse("[day05]\npart3 = 1").unwrap_err().to_string(),
+            "input: expected `part1` or `part2`, found \"part3\""
+        );
+        assert!(parse("[day05]\npart1 = ").is_err());
+    }
+
+    #[test]
+    fn check_works() {
+        assert_eq!(check(Some("13"), &Ok("13".to_string())), Verdict::Pass);
+        assert_eq!(check(None, &Ok("13".to_string())), Verdict::Missing);
+        assert_eq!(
+            check(Some("13"), &Ok("14".to_string())),
+            Verdict::Fail {
+                expected: "13".to_string(),
+                actual: "14".to_string()
+            }
+        );
+        assert_eq!(
+            check(Some("13"), &Err("boom".to_string())),
+            Verdict::Error("boom".to_string())
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    hint::black_box,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use crate::{cli, error::Result, runner};
+
+pub const USAGE: &str = "usage: aoc bench <day>|<from>..<to>|<from>..=<to>|--all [--iterations <n>] [--json] [--input <path>|-]";
+
+const DEFAULT_ITERATIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats {
+            mean: samples.iter().sum::<Duration>() / n as u32,
+            median,
+            min: samples[0],
+        }
+    }
+}
+
+/// How long each step of a day took.
+pub struct Timings {
+    pub day: u32,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Time `iterations` runs of `f`, which must be at least one.
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&mut samples))
+}
+
+/// The timings as a JSON array, one object per day, in nanoseconds.
+pub fn json(timings: &[Timings]) -> String {
+    let stats = |s: &Stats| {
+        format!(
+            "{{\"mean_ns\": {}, \"median_ns\": {}, \"min_ns\": {}}}",
+            s.mean.as_nanos(),
+            s.median.as_nanos(),
+            s.min.as_nanos()
+        )
+    };
+    let days = timings
+        .iter()
+        .map(|t| {
+            format!(
+                "  {{\"day\": {}, \"parse\": {}, \"part1\": {}, \"part2\": {}}}",
+                t.day,
+                stats(&t.parse),
+                stats(&t.part1),
+                stats(&t.part2)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]\n", days.join(",\n"))
+}
+
+/// The timings as a table, one row per step.
+pub fn table(timings: &[Timings]) -> String {
+    let mut out = "Day | Step  | Mean       | Median     | Min\n".to_string();
+    out += "----+-------+------------+------------+-----------\n";
+    for t in timings {
+        for (step, s) in [
+            ("parse", &t.parse),
+            ("part1", &t.part1),
+            ("part2", &t.part2),
+        ] {
+            out += &format!(
+                "{:>3} | {:5} | {:>10} | {:>10} | {:>10}\n",
+                t.day,
+                step,
+                format!("{:.1?}", s.mean),
+                format!("{:.1?}", s.median),
+                format!("{:.1?}", s.min)
+            );
+        }
+    }
+    out
+}
+
+type Options = (usize, bool, runner::Selected);
+
+fn options(args: &[String]) -> std::result::Result<Options, String> {
+    let mut args = args.to_vec();
+    let iterations = match args.iter().position(|a| a == "--iterations") {
+        Some(i) if i + 1 < args.len() => {
+            let n = args.remove(i + 1);
+            args.remove(i);
+            match n.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(format!("'{}' is not a number of iterations", n)),
+            }
+        }
+        Some(_) => return Err("--iterations needs a number".to_string()),
+        None => DEFAULT_ITERATIONS,
+    };
+    let json = match args.iter().position(|a| a == "--json") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let source = cli::take_input(&mut args)?;
+    if args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    Ok((iterations, json, runner::select(&args, source)?))
+}
+
+/// The `aoc bench` command, also behind `cargo bench`. Days that fail are
+/// reported on stderr and left out of the results.
+pub fn main(args: &[String]) -> ExitCode {
+    let (iterations, json_output, days) = match options(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut timings = Vec::new();
+    let mut failed = false;
+    for (day, source) in days {
+        match runner::bench(day.as_ref(), source.as_deref(), iterations) {
+            Ok(t) => timings.push(t),
+            Err(e) => {
+                eprintln!("day {}: {}", day.day(), e);
+                failed = true;
+            }
+        }
+    }
+    if json_output {
+        print!("{}", json(&timings));
+    } else {
+        print!("{}", table(&timings));
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats_works() {
+        let s = Stats::from_samples(&mut [ms(4), ms(1), ms(10)]);
+        assert_eq!(
+            s,
+            Stats {
+                mean: ms(5),
+                median: ms(4),
+                min: ms(1)
+            }
+        );
+        let s = Stats::from_samples(&mut [ms(4), ms(1), ms(10), ms(2)]);
+        assert_eq!(s.median, ms(3));
+    }
+}
@@ -0,0 +1,53 @@
+use std::{env, process::ExitCode};
+
+use aoc::{
+    answers, bench, cache, cli, examples,
+    runner::{self, Outcome},
+    scaffold,
+};
+
+const USAGE: &str = "usage: aoc run <day>|<from>..<to>|<from>..=<to>|--all [--input <path>|-]
+       aoc bench <day>|<from>..<to>|<from>..=<to>|--all [--iterations <n>] [--json] [--input <path>|-]
+       aoc verify [<day>|<from>..<to>|<from>..=<to>|--all]
+       aoc examples [<day>|<from>..<to>|<from>..=<to>|--all]
+       aoc fetch <day>|<from>..<to>|<from>..=<to>...
+       aoc new <day>";
+
+fn run(args: &[String]) -> Result<Vec<Outcome>, String> {
+    let mut args = args.to_vec();
+    let source = cli::take_input(&mut args)?;
+    if args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    Ok(runner::select(&args, source)?
+        .into_iter()
+        .map(|(day, source)| runner::run(day.as_ref(), source.as_deref()))
+        .collect())
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("bench") => return bench::main(&args[1..]),
+        Some("verify") => return answers::main(&args[1..]),
+        Some("examples") => return examples::main(&args[1..]),
+        Some("fetch") => return cache::main(&args[1..]),
+        Some("new") => return scaffold::main(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(outcomes) => {
+            print!("{}", runner::table(&outcomes));
+            if outcomes.iter().any(|o| o.failed()) {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(2)
+        }
+    }
+}
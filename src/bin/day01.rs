@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day01::Day01};
+
+fn main() -> ExitCode {
+    cli::main(&Day01)
+}
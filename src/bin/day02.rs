@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day02::Day02};
+
+fn main() -> ExitCode {
+    cli::main(&Day02)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day03::Day03};
+
+fn main() -> ExitCode {
+    cli::main(&Day03)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day04::Day04};
+
+fn main() -> ExitCode {
+    cli::main(&Day04)
+}
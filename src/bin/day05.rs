@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day05::Day05};
+
+fn main() -> ExitCode {
+    cli::main(&Day05)
+}
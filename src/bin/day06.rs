@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day06::Day06};
+
+fn main() -> ExitCode {
+    cli::main(&Day06)
+}
@@ -0,0 +1,37 @@
+use std::{env, path::Path, process::ExitCode};
+
+use aoc::{
+    cli,
+    day07::{self, Day07, Dir},
+    error,
+};
+
+const USAGE: &str =
+    "usage: day07 [--transcript <dir> | --synthetic <dirs> [<seed>] | --input <path>|-]";
+
+/// `--transcript` prints a terminal session exploring a real directory, and
+/// `--synthetic` one exploring a random tree of that many directories, in
+/// the puzzle's format. Either can be fed back in with `--input`.
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let dir = match args[..] {
+        ["--transcript", path] => Dir::read(Path::new(path)),
+        ["--synthetic", dirs] => match dirs.parse() {
+            Ok(dirs) => Ok(Dir::synthetic(dirs, 0)),
+            Err(_) => return usage(),
+        },
+        ["--synthetic", dirs, seed] => match (dirs.parse(), seed.parse()) {
+            (Ok(dirs), Ok(seed)) => Ok(Dir::synthetic(dirs, seed)),
+            _ => return usage(),
+        },
+        [first, ..] if first == "--transcript" || first == "--synthetic" => return usage(),
+        _ => return cli::main(&Day07),
+    };
+    error::report(dir.map(|dir| print!("{}", day07::transcript(&dir))))
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{}", USAGE);
+    ExitCode::from(2)
+}
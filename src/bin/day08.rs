@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day08::Day08};
+
+fn main() -> ExitCode {
+    cli::main(&Day08)
+}
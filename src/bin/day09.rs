@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day09::Day09};
+
+fn main() -> ExitCode {
+    cli::main(&Day09)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day10::Day10};
+
+fn main() -> ExitCode {
+    cli::main(&Day10)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day11::Day11};
+
+fn main() -> ExitCode {
+    cli::main(&Day11)
+}
@@ -0,0 +1,59 @@
+use std::{env, fs, process::ExitCode};
+
+use aoc::{
+    cache, cli,
+    day12::{self, Day12},
+    error::{self, AocError},
+    render, Solution,
+};
+
+const USAGE: &str = "usage: day12 [--render [<image.ppm>]] [--input <path>|-]";
+
+/// Pixels to a cell in the rendered image.
+const SCALE: usize = 6;
+
+/// `--render` draws the part 1 search, writing a PPM image of it (to
+/// `day12.ppm` unless another path is given) and printing it in colour.
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let Some(i) = args.iter().position(|a| a == "--render") else {
+        return cli::main(&Day12::default());
+    };
+    args.remove(i);
+    let image_path = match args.get(i) {
+        Some(a) if !a.starts_with('-') => args.remove(i),
+        _ => "day12.ppm".to_string(),
+    };
+    let source = match cli::take_input(&mut args) {
+        Ok(source) if args.is_empty() => source,
+        Ok(_) => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    error::report(
+        cache::open(Day12::DAY, source.as_deref()).and_then(|(input, name)| {
+            let day = Day12::default();
+            let altitudes = day.parse(&input).map_err(|e| e.in_file(name))?;
+            let search = day.climb_from_start(&altitudes)?;
+            let image = day12::shade(&altitudes, &search);
+            fs::write(&image_path, render::ppm(&image, SCALE)).map_err(|source| AocError::Io {
+                path: image_path.clone().into(),
+                source,
+            })?;
+            let map = day12::route_map(&altitudes, &search.path);
+            print!("{}", render::ansi(&map, &image));
+            println!(
+                "{} steps, {} cells visited; image written to {}",
+                search.cost,
+                search.visited.len(),
+                image_path
+            );
+            Ok(())
+        }),
+    )
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day13::Day13};
+
+fn main() -> ExitCode {
+    cli::main(&Day13)
+}
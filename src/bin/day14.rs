@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day14::Day14};
+
+fn main() -> ExitCode {
+    cli::main(&Day14)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day15::Day15};
+
+fn main() -> ExitCode {
+    cli::main(&Day15::default())
+}
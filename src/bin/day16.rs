@@ -0,0 +1,52 @@
+use std::{env, process::ExitCode};
+
+use aoc::{
+    cache, cli,
+    day16::{self, Day16, Network},
+    error, Solution,
+};
+
+const USAGE: &str = "usage: day16 [--dot [--team]] [--input <path>|-]";
+
+/// `--dot` prints the valves and tunnels as a Graphviz graph with the part 1
+/// route drawn on, or with `--team` each part 2 agent's route. Render it with
+/// e.g. `day16 --dot | dot -Tsvg > valves.svg`.
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let Some(i) = args.iter().position(|a| a == "--dot") else {
+        return cli::main(&Day16::default());
+    };
+    args.remove(i);
+    let team = match args.iter().position(|a| a == "--team") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let source = match cli::take_input(&mut args) {
+        Ok(source) if args.is_empty() => source,
+        Ok(_) => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    error::report(
+        cache::open(Day16::DAY, source.as_deref()).and_then(|(input, name)| {
+            let day = Day16::default();
+            let valves = day.parse(&input).map_err(|e| e.in_file(name))?;
+            let network = Network::new(&valves, day16::START)?;
+            let schedules = if team {
+                network.plan_team(day.agents, day.team_minutes)?.schedules
+            } else {
+                vec![network.plan(day.minutes, network.all())]
+            };
+            print!("{}", day16::dot(&valves, day16::START, &schedules)?);
+            Ok(())
+        }),
+    )
+}
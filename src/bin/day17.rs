@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day17::Day17};
+
+fn main() -> ExitCode {
+    cli::main(&Day17::default())
+}
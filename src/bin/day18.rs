@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day18::Day18};
+
+fn main() -> ExitCode {
+    cli::main(&Day18)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day19::Day19};
+
+fn main() -> ExitCode {
+    cli::main(&Day19)
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use aoc::{cli, day20::Day20};
+
+fn main() -> ExitCode {
+    cli::main(&Day20)
+}
@@ -0,0 +1,313 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+};
+
+use crate::{
+    error::{AocError, Result},
+    input, runner,
+};
+
+pub const DIR: &str = "data";
+
+pub const USAGE: &str = "usage: aoc fetch <day>|<from>..<to>|<from>..=<to>...";
+
+/// The puzzle inputs and examples kept in `data/`. Each day's real input is
+/// `DayNN.txt`, and a named variant such as an example is `DayNN_<variant>.txt`.
+/// Lookups ignore case, since the files have been copied in by hand.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache::new(DIR)
+    }
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Cache {
+        Cache { dir: dir.into() }
+    }
+
+    /// Where `day`'s input, or the named variant of it, belongs.
+    pub fn canonical(&self, day: u32, variant: Option<&str>) -> PathBuf {
+        let name = match variant {
+            Some(v) => format!("Day{:02}_{}.txt", day, v),
+            None => format!("Day{:02}.txt", day),
+        };
+        self.dir.join(name)
+    }
+
+    /// Find `day`'s input, or the named variant of it, whatever case its file
+    /// name is in.
+    pub fn resolve(&self, day: u32, variant: Option<&str>) -> Result<PathBuf> {
+        let canonical = self.canonical(day, variant);
+        if canonical.is_file() {
+            return Ok(canonical);
+        }
+        let wanted = canonical
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_lowercase();
+        fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|p| {
+                p.is_file()
+                    && p.file_name()
+                        .map(|n| n.to_string_lossy().to_lowercase() == wanted)
+                        .unwrap_or(false)
+            })
+            .ok_or(AocError::MissingInput {
+                day,
+                variant: variant.map(|v| v.to_string()),
+                path: canonical,
+            })
+    }
+
+    pub fn load(&self, day: u32, variant: Option<&str>) -> Result<String> {
+        input::load(self.resolve(day, variant)?)
+    }
+
+    /// Make sure `day`'s real input is cached, downloading it with `fetch`
+    /// if it isn't.
+    pub fn fetch(&self, day: u32, fetch: &dyn Fetch) -> Result<PathBuf> {
+        if let Ok(path) = self.resolve(day, None) {
+            return Ok(path);
+        }
+        let input = fetch.fetch(day)?;
+        let path = self.canonical(day, None);
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, input))
+            .map_err(|source| AocError::Io {
+                path: path.clone(),
+                source,
+            })?;
+        Ok(path)
+    }
+}
+
+/// Read `day`'s input from `source`, a path or `-` for stdin, or else from
+/// the cache. Returns the input along with the name to report errors against.
+pub fn open(day: u32, source: Option<&str>) -> Result<(String, String)> {
+    match source {
+        Some(source) => Ok((input::open(source)?, input::source_name(source).to_string())),
+        None => {
+            let cache = Cache::default();
+            let path = cache.resolve(day, None)?;
+            Ok((input::load(&path)?, path.display().to_string()))
+        }
+    }
+}
+
+/// Somewhere to download puzzle inputs from.
+pub trait Fetch {
+    fn fetch(&self, day: u32) -> Result<String>;
+}
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// adventofcode.com, signed in with the `session` cookie of a logged-in
+/// browser. Downloads with `curl`.
+pub struct Session {
+    token: String,
+    base_url: String,
+}
+
+impl Session {
+    pub fn new(token: &str) -> Session {
+        Session {
+            token: token.trim().to_string(),
+            base_url: "https://adventofcode.com/2022".to_string(),
+        }
+    }
+
+    /// A session using the token in `$AOC_SESSION`.
+    pub fn from_env() -> Result<Session> {
+        env::var(SESSION_VAR)
+            .map(|token| Session::new(&token))
+            .map_err(|_| AocError::Fetch {
+                day: None,
+                message: format!(
+                    "set {} to your adventofcode.com session cookie",
+                    SESSION_VAR
+                ),
+            })
+    }
+
+    /// The curl command that downloads `url`, with the config to write to
+    /// its stdin. The cookie goes in the config rather than on the command
+    /// line, where anyone on the machine could read it through `ps`.
+    fn curl(&self, url: &str) -> (Command, String) {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--fail", "--config", "-"])
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let token = self.token.replace('\\', "\\\\").replace('"', "\\\"");
+        (command, format!("cookie = \"session={}\"\n", token))
+    }
+}
+
+impl Fetch for Session {
+    fn fetch(&self, day: u32) -> Result<String> {
+        let url = format!("{}/day/{}/input", self.base_url, day);
+        let error = |message: String| AocError::Fetch {
+            day: Some(day),
+            message,
+        };
+        let (mut command, config) = self.curl(&url);
+        let output = command
+            .spawn()
+            .and_then(|mut curl| {
+                // Dropping stdin once it's written closes it, ending the config
+                curl.stdin.take().unwrap().write_all(config.as_bytes())?;
+                curl.wait_with_output()
+            })
+            .map_err(|e| error(format!("couldn't run curl: {}", e)))?;
+        if !output.status.success() {
+            return Err(error(format!(
+                "{}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        String::from_utf8(output.stdout).map_err(|_| error(format!("{}: not UTF-8", url)))
+    }
+}
+
+/// A local directory laid out like the site, with each input at
+/// `day/<n>/input`. Stands in for adventofcode.com in tests and offline.
+pub struct Mirror {
+    root: PathBuf,
+}
+
+impl Mirror {
+    pub fn new(root: impl AsRef<Path>) -> Mirror {
+        Mirror {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl Fetch for Mirror {
+    fn fetch(&self, day: u32) -> Result<String> {
+        let path = self.root.join("day").join(day.to_string()).join("input");
+        fs::read_to_string(&path).map_err(|e| AocError::Fetch {
+            day: Some(day),
+            message: format!("{}: {}", path.display(), e),
+        })
+    }
+}
+
+/// The `aoc fetch` command: download any of the given days' inputs that
+/// aren't cached yet, signed in with `$AOC_SESSION`.
+pub fn main(args: &[String]) -> ExitCode {
+    let mut days = Vec::new();
+    for a in args {
+        match runner::parse_selection(a) {
+            Ok(selection) => days.extend(selection),
+            Err(e) => {
+                eprintln!("{}\n{}", e, USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    if days.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    }
+    let session = match Session::from_env() {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let cache = Cache::default();
+    let mut failed = false;
+    for day in days {
+        match cache.fetch(day, &session) {
+            Ok(path) => println!("day {}: {}", day, path.display()),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_works() {
+        let dir = scratch("resolve");
+        fs::write(dir.join("Day01.txt"), "1").unwrap();
+        fs::write(dir.join("day03_TEST.txt"), "3").unwrap();
+        let cache = Cache::new(&dir);
+
+        assert_eq!(cache.resolve(1, None).unwrap(), dir.join("Day01.txt"));
+        assert_eq!(cache.load(3, Some("test")).unwrap(), "3");
+        assert_eq!(
+            cache.resolve(2, Some("test")).unwrap_err().to_string(),
+            format!(
+                "no test input for day 2: {} doesn't exist",
+                dir.join("Day02_test.txt").display()
+            )
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_works() {
+        let dir = scratch("fetch");
+        let site = dir.join("site");
+        fs::create_dir_all(site.join("day/7")).unwrap();
+        fs::write(site.join("day/7/input"), "$ cd /\n").unwrap();
+        let cache = Cache::new(dir.join("data"));
+        let mirror = Mirror::new(&site);
+
+        let path = cache.fetch(7, &mirror).unwrap();
+        assert_eq!(path, dir.join("data/Day07.txt"));
+        assert_eq!(cache.load(7, None).unwrap(), "$ cd /");
+        // Already cached, so the mirror isn't asked again
+        fs::remove_dir_all(&site).unwrap();
+        assert_eq!(cache.fetch(7, &mirror).unwrap(), path);
+        assert!(cache.fetch(8, &mirror).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn session_keeps_token_off_the_command_line() {
+        let session = Session::new("53616c7465645f5f\n");
+        let (command, config) = session.curl("https://example.com/day/1/input");
+        assert!(command
+            .get_args()
+            .all(|a| !a.to_string_lossy().contains("53616c")));
+        assert_eq!(config, "cookie = \"session=53616c7465645f5f\"\n");
+        let (_, config) = Session::new("a\"b").curl("https://example.com");
+        assert_eq!(config, "cookie = \"session=a\\\"b\"\n");
+    }
+}
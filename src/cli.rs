@@ -0,0 +1,68 @@
+use std::{env, process::ExitCode};
+
+use crate::{cache, error, solution, Solution};
+
+pub const INPUT_USAGE: &str = "[--input <path>|-]";
+
+/// Pull `--input <path>`, or a bare `-` for stdin, out of `args`, leaving
+/// everything else for the caller.
+pub fn take_input(args: &mut Vec<String>) -> Result<Option<String>, String> {
+    if let Some(i) = args.iter().position(|a| a == "--input") {
+        if i + 1 >= args.len() {
+            return Err("--input needs a path, or - for stdin".to_string());
+        }
+        let source = args.remove(i + 1);
+        args.remove(i);
+        Ok(Some(source))
+    } else if let Some(i) = args.iter().position(|a| a == "-") {
+        Ok(Some(args.remove(i)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// What each day's binary does: read the input named on the command line,
+/// or the day's cached input, and print both parts.
+pub fn main<S: Solution>(solution: &S) -> ExitCode {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let source = match take_input(&mut args) {
+        Ok(source) if args.is_empty() => source,
+        Ok(_) => {
+            eprintln!("usage: day{:02} {}", S::DAY, INPUT_USAGE);
+            return ExitCode::from(2);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+    error::report(
+        cache::open(S::DAY, source.as_deref()).and_then(|(input, name)| {
+            solution::print_answers(solution, &input).map_err(|e| e.in_file(name))
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn take_input_works() {
+        let mut a = args("run 7 --input other.txt");
+        assert_eq!(take_input(&mut a), Ok(Some("other.txt".to_string())));
+        assert_eq!(a, args("run 7"));
+
+        let mut a = args("-");
+        assert_eq!(take_input(&mut a), Ok(Some("-".to_string())));
+        assert!(a.is_empty());
+
+        let mut a = args("run 7");
+        assert_eq!(take_input(&mut a), Ok(None));
+        assert!(take_input(&mut args("--input")).is_err());
+    }
+}
@@ -1,59 +1,67 @@
-use core::str::Split;
 use itertools::rev;
 use itertools::sorted;
-use itertools::Itertools;
-use std::fs;
-
-fn sum_group(iter: &mut Split<&str>) -> Option<i32> {
-    match iter.next() {
-        None => None,
-        Some("") => Some(0),
-        Some(s) => Some(
-            s.parse::<i32>().unwrap()
-                + match sum_group(iter) {
-                    None => 0,
-                    Some(x) => x,
-                },
-        ),
+
+use crate::{
+    error::{AocError, Result},
+    input::parse_lines,
+    Solution,
+};
+
+pub struct Day01;
+
+fn calories(line: &str) -> Result<Option<i32>> {
+    if line.is_empty() {
+        Ok(None)
+    } else {
+        line.parse::<i32>()
+            .map(Some)
+            .map_err(|_| AocError::parse(line, "a calorie count or a blank line"))
     }
 }
 
-fn part1(name: &str) -> i32 {
-    return fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .batching(sum_group)
-        .max()
-        .unwrap_or(0);
-}
+impl Solution for Day01 {
+    const DAY: u32 = 1;
 
-fn part2(name: &str) -> i32 {
-    return rev(sorted(
-        fs::read_to_string(name)
-            .expect("Couldn't open file")
-            .split("\r\n")
-            .batching(sum_group),
-    ))
-    .take(3)
-    .sum();
-}
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>> {
+        let items = parse_lines(input, calories)?;
+        // Each elf's items are separated from the next elf's by a blank line
+        Ok(items
+            .split(|s| s.is_none())
+            .map(|group| group.iter().flatten().sum())
+            .collect())
+    }
+
+    fn part1(&self, groups: &Vec<i32>) -> Result<i32> {
+        Ok(groups.iter().copied().max().unwrap_or(0))
+    }
 
-fn main() {
-    println!("Part 1: {}", part1("data/Day01.txt"));
-    println!("Part 2: {}", part2("data/Day01.txt"));
+    fn part2(&self, groups: &Vec<i32>) -> Result<i32> {
+        Ok(rev(sorted(groups.iter())).take(3).sum())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
+
+    fn input() -> Vec<i32> {
+        Day01
+            .parse(&load("data/Day01_test.txt").expect("Couldn't open file"))
+            .unwrap()
+    }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("data/Day01_test.txt"), 24000);
+        assert_eq!(Day01.part1(&input()).unwrap(), 24000);
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("data/Day01_test.txt"), 45000);
+        assert_eq!(Day01.part2(&input()).unwrap(), 45000);
     }
 }
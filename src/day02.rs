@@ -1,194 +1,191 @@
-use std::fs;
-
 use regex::Regex;
 
+use crate::{
+    error::{AocError, Result},
+    input::parse_lines,
+    Solution,
+};
+
+pub struct Day02;
+
 #[derive(Clone, Copy)]
 enum Move {
-    ROCK,
-    PAPER,
-    SCISSORS,
+    Rock,
+    Paper,
+    Scissors,
 }
 
 #[derive(Clone, Copy)]
 enum WantResult {
-    WIN,
-    LOSE,
-    DRAW,
-}
-
-trait Score {
-    fn score(self: &Self) -> i32;
-    fn win_lose(self: &Self, opponent: &Move) -> i32;
+    Win,
+    Lose,
+    Draw,
 }
 
 impl Move {
-    fn score(self: &Self) -> i32 {
+    fn score(&self) -> i32 {
         match self {
-            Move::ROCK => 1,
-            Move::PAPER => 2,
-            Move::SCISSORS => 3,
+            Move::Rock => 1,
+            Move::Paper => 2,
+            Move::Scissors => 3,
         }
     }
 
-    fn win_lose(self: &Self, opponent: &Move) -> i32 {
+    fn win_lose(&self, opponent: &Move) -> i32 {
         match (self, opponent) {
-            (Move::ROCK, Move::ROCK) => 3,
-            (Move::ROCK, Move::PAPER) => 0,
-            (Move::ROCK, Move::SCISSORS) => 6,
-            (Move::PAPER, Move::ROCK) => 6,
-            (Move::PAPER, Move::PAPER) => 3,
-            (Move::PAPER, Move::SCISSORS) => 0,
-            (Move::SCISSORS, Move::ROCK) => 0,
-            (Move::SCISSORS, Move::PAPER) => 6,
-            (Move::SCISSORS, Move::SCISSORS) => 3,
+            (Move::Rock, Move::Rock) => 3,
+            (Move::Rock, Move::Paper) => 0,
+            (Move::Rock, Move::Scissors) => 6,
+            (Move::Paper, Move::Rock) => 6,
+            (Move::Paper, Move::Paper) => 3,
+            (Move::Paper, Move::Scissors) => 0,
+            (Move::Scissors, Move::Rock) => 0,
+            (Move::Scissors, Move::Paper) => 6,
+            (Move::Scissors, Move::Scissors) => 3,
         }
     }
 }
 
-struct Play {
+pub struct Play {
     opponent: Move,
     mine: Move,
 }
 
 impl Play {
-    fn score(self: &Self) -> i32 {
-        return self.mine.score() + self.mine.win_lose(&self.opponent);
+    fn score(&self) -> i32 {
+        self.mine.score() + self.mine.win_lose(&self.opponent)
     }
 }
 
-#[derive(Debug)]
-struct ParsePlayError {
-    _o: String,
-}
+const ROUND: &str = "`<A|B|C> <X|Y|Z>`";
 
 impl std::str::FromStr for Play {
-    type Err = ParsePlayError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new("([ABC]) ([XYZ])").unwrap();
-        let cap = re.captures(s).unwrap();
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self> {
+        let re = Regex::new("^([ABC]) ([XYZ])$").unwrap();
+        let cap = re.captures(s).ok_or_else(|| AocError::parse(s, ROUND))?;
         let opponent = match &cap[1] {
-            "A" => Some(Move::ROCK),
-            "B" => Some(Move::PAPER),
-            "C" => Some(Move::SCISSORS),
+            "A" => Some(Move::Rock),
+            "B" => Some(Move::Paper),
+            "C" => Some(Move::Scissors),
             _ => None,
         };
         let mine = match &cap[2] {
-            "X" => Some(Move::ROCK),
-            "Y" => Some(Move::PAPER),
-            "Z" => Some(Move::SCISSORS),
+            "X" => Some(Move::Rock),
+            "Y" => Some(Move::Paper),
+            "Z" => Some(Move::Scissors),
             _ => None,
         };
 
         if let (Some(opponent), Some(mine)) = (opponent, mine) {
-            Ok(Play {
-                opponent: opponent,
-                mine: mine,
-            })
+            Ok(Play { opponent, mine })
         } else {
-            Err(ParsePlayError {
-                _o: String::from(s),
-            })
+            Err(AocError::parse(s, ROUND))
         }
     }
 }
 
-struct MatchStrategy {
+pub struct MatchStrategy {
     opponent: Move,
     want: WantResult,
 }
 
 impl Move {
-    fn move_for_result(self: &Self, result: WantResult) -> Move {
+    fn move_for_result(&self, result: WantResult) -> Move {
         match (self, result) {
-            (x, WantResult::DRAW) => *x,
-            (Move::ROCK, WantResult::LOSE) => Move::SCISSORS,
-            (Move::ROCK, WantResult::WIN) => Move::PAPER,
-            (Move::PAPER, WantResult::LOSE) => Move::ROCK,
-            (Move::PAPER, WantResult::WIN) => Move::SCISSORS,
-            (Move::SCISSORS, WantResult::LOSE) => Move::PAPER,
-            (Move::SCISSORS, WantResult::WIN) => Move::ROCK,
+            (x, WantResult::Draw) => *x,
+            (Move::Rock, WantResult::Lose) => Move::Scissors,
+            (Move::Rock, WantResult::Win) => Move::Paper,
+            (Move::Paper, WantResult::Lose) => Move::Rock,
+            (Move::Paper, WantResult::Win) => Move::Scissors,
+            (Move::Scissors, WantResult::Lose) => Move::Paper,
+            (Move::Scissors, WantResult::Win) => Move::Rock,
         }
     }
 }
 
 impl std::str::FromStr for MatchStrategy {
-    type Err = ParsePlayError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new("([ABC]) ([XYZ])").unwrap();
-        let cap = re.captures(s).unwrap();
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self> {
+        let re = Regex::new("^([ABC]) ([XYZ])$").unwrap();
+        let cap = re.captures(s).ok_or_else(|| AocError::parse(s, ROUND))?;
         let opponent = match &cap[1] {
-            "A" => Some(Move::ROCK),
-            "B" => Some(Move::PAPER),
-            "C" => Some(Move::SCISSORS),
+            "A" => Some(Move::Rock),
+            "B" => Some(Move::Paper),
+            "C" => Some(Move::Scissors),
             _ => None,
         };
         let want = match &cap[2] {
-            "X" => Some(WantResult::LOSE),
-            "Y" => Some(WantResult::DRAW),
-            "Z" => Some(WantResult::WIN),
+            "X" => Some(WantResult::Lose),
+            "Y" => Some(WantResult::Draw),
+            "Z" => Some(WantResult::Win),
             _ => None,
         };
 
         if let (Some(opponent), Some(want)) = (opponent, want) {
             Ok(MatchStrategy { opponent, want })
         } else {
-            Err(ParsePlayError {
-                _o: String::from(s),
-            })
+            Err(AocError::parse(s, ROUND))
         }
     }
 }
 
 impl WantResult {
-    fn score(self: &Self) -> i32 {
+    fn score(&self) -> i32 {
         match self {
-            WantResult::LOSE => 0,
-            WantResult::DRAW => 3,
-            WantResult::WIN => 6,
+            WantResult::Lose => 0,
+            WantResult::Draw => 3,
+            WantResult::Win => 6,
         }
     }
 }
 
 impl MatchStrategy {
-    fn score(self: &Self) -> i32 {
+    fn score(&self) -> i32 {
         self.want.score() + self.opponent.move_for_result(self.want).score()
     }
 }
 
-fn part1(name: &str) -> i32 {
-    fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .filter(|s| s.len() > 0)
-        .map(|s| s.parse::<Play>().unwrap().score())
-        .sum()
-}
+impl Solution for Day02 {
+    const DAY: u32 = 2;
 
-fn part2(name: &str) -> i32 {
-    fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .filter(|s| s.len() > 0)
-        .map(|s| s.parse::<MatchStrategy>().unwrap().score())
-        .sum()
-}
+    type Input = Vec<(Play, MatchStrategy)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-fn main() {
-    println!("Part 1: {}", part1("data/Day02.txt"));
-    println!("Part 2: {}", part2("data/Day02.txt"));
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_lines(input, |s| {
+            Ok((s.parse::<Play>()?, s.parse::<MatchStrategy>()?))
+        })
+    }
+
+    fn part1(&self, rounds: &Self::Input) -> Result<i32> {
+        Ok(rounds.iter().map(|(play, _)| play.score()).sum())
+    }
+
+    fn part2(&self, rounds: &Self::Input) -> Result<i32> {
+        Ok(rounds.iter().map(|(_, strategy)| strategy.score()).sum())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
+
+    fn input() -> Vec<(Play, MatchStrategy)> {
+        Day02
+            .parse(&load("data/Day02_test.txt").expect("Couldn't open file"))
+            .unwrap()
+    }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("data/Day02_test.txt"), 15);
+        assert_eq!(Day02.part1(&input()).unwrap(), 15);
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("data/Day02_test.txt"), 12);
+        assert_eq!(Day02.part2(&input()).unwrap(), 12);
     }
 }
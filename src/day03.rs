@@ -1,6 +1,10 @@
-use std::{fs, str::Split};
+use crate::{
+    error::{AocError, Result},
+    input::parse_lines,
+    Solution,
+};
 
-use itertools::{Chunk, Itertools};
+pub struct Day03;
 
 fn priority(c: char) -> u32 {
     if c.is_lowercase() {
@@ -10,65 +14,77 @@ fn priority(c: char) -> u32 {
     }
 }
 
-fn item_priorities(line: &str) -> Vec<u32> {
-    line.chars().map(priority).collect()
+fn item_priorities(line: &str) -> Result<Vec<u32>> {
+    if line.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(line.chars().map(priority).collect())
+    } else {
+        Err(AocError::parse(line, "a line of item letters"))
+    }
 }
 
-fn misplaced_item_priority(line: &str) -> u32 {
-    let priorities = item_priorities(line);
-    let l = line.len() / 2;
-    let front: Vec<u32> = priorities.iter().take(l).map(|s| *s).collect();
-    let back: Vec<u32> = priorities.into_iter().skip(l).collect();
+fn misplaced_item_priority((idx, priorities): (usize, &Vec<u32>)) -> Result<u32> {
+    let (front, back) = priorities.split_at(priorities.len() / 2);
     front
-        .into_iter()
-        .find(|s| back.iter().any(|f| *s == *f))
-        .unwrap()
-}
-
-fn badge_for_group(g: Chunk<Split<&str>>) -> u32 {
-    let rows: Vec<Vec<u32>> = g.into_iter().map(item_priorities).collect();
-    rows[0]
         .iter()
-        .filter(|s| rows[1].iter().any(|f| **s == *f))
-        .map(|s| *s)
-        .find(|s| rows[2].iter().any(|f| *s == *f))
-        .unwrap()
+        .copied()
+        .find(|s| back.contains(s))
+        .ok_or_else(|| AocError::solve(format!("rucksack {} has no misplaced item", idx + 1)))
 }
 
-fn part1(name: &str) -> u32 {
-    fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .map(misplaced_item_priority)
-        .sum()
+fn badge_for_group((idx, rows): (usize, &[Vec<u32>])) -> Result<u32> {
+    match rows {
+        [a, b, c] => a
+            .iter()
+            .copied()
+            .filter(|s| b.contains(s))
+            .find(|s| c.contains(s)),
+        _ => None,
+    }
+    .ok_or_else(|| AocError::solve(format!("group {} has no common badge", idx + 1)))
 }
 
-fn part2(name: &str) -> u32 {
-    fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .chunks(3)
-        .into_iter()
-        .map(badge_for_group)
-        .sum()
-}
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Vec<u32>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-fn main() {
-    println!("Part 1: {}", part1("data/Day03.txt"));
-    println!("Part 2: {}", part2("data/Day03.txt"));
+    fn parse(&self, input: &str) -> Result<Vec<Vec<u32>>> {
+        parse_lines(input, item_priorities)
+    }
+
+    fn part1(&self, rucksacks: &Vec<Vec<u32>>) -> Result<u32> {
+        rucksacks
+            .iter()
+            .enumerate()
+            .map(misplaced_item_priority)
+            .sum()
+    }
+
+    fn part2(&self, rucksacks: &Vec<Vec<u32>>) -> Result<u32> {
+        rucksacks.chunks(3).enumerate().map(badge_for_group).sum()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
+
+    fn input() -> Vec<Vec<u32>> {
+        Day03
+            .parse(&load("data/Day03_test.txt").expect("Couldn't open file"))
+            .unwrap()
+    }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("data/Day03_test.txt"), 157);
+        assert_eq!(Day03.part1(&input()).unwrap(), 157);
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("data/Day03_test.txt"), 70);
+        assert_eq!(Day03.part2(&input()).unwrap(), 70);
     }
 }
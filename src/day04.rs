@@ -1,58 +1,69 @@
 use regex::Regex;
-use std::fs;
-
-fn parse_ranges(s: &str) -> ((u32, u32), (u32, u32)) {
-    let re = Regex::new("([0-9]+)-([0-9]+),([0-9]+)-([0-9]+)").unwrap();
-    let cap = re.captures(s).unwrap();
-    let a1 = cap[1].parse::<u32>().unwrap();
-    let a2 = cap[2].parse::<u32>().unwrap();
-    let a3 = cap[3].parse::<u32>().unwrap();
-    let a4 = cap[4].parse::<u32>().unwrap();
-    ((a1, a2), (a3, a4))
+
+use crate::{
+    error::{AocError, Result},
+    input::parse_lines,
+    Solution,
+};
+
+pub struct Day04;
+
+type Assignment = ((u32, u32), (u32, u32));
+
+fn parse_ranges(s: &str) -> Result<Assignment> {
+    let re = Regex::new("^([0-9]+)-([0-9]+),([0-9]+)-([0-9]+)$").unwrap();
+    let error = || AocError::parse(s, "`<from>-<to>,<from>-<to>`");
+    let cap = re.captures(s).ok_or_else(error)?;
+    let n = |i: usize| cap[i].parse::<u32>().map_err(|_| error());
+    Ok(((n(1)?, n(2)?), (n(3)?, n(4)?)))
 }
 
-fn contained(((a1, a2), (b1, b2)): &((u32, u32), (u32, u32))) -> bool {
+fn contained(((a1, a2), (b1, b2)): &&Assignment) -> bool {
     (a1 <= b1 && a2 >= b2) || (b1 <= a1 && b2 >= a2)
 }
 
-fn overlaps(((a1, a2), (b1, b2)): &((u32, u32), (u32, u32))) -> bool {
+fn overlaps(((a1, a2), (b1, b2)): &&Assignment) -> bool {
     (a1 <= b1 && b1 <= a2) || (b1 <= a1 && a1 <= b2)
 }
 
-fn part1(name: &str) -> u32 {
-    fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .map(parse_ranges)
-        .filter(contained)
-        .count() as u32
-}
+impl Solution for Day04 {
+    const DAY: u32 = 4;
 
-fn part2(name: &str) -> u32 {
-    fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .split("\r\n")
-        .map(parse_ranges)
-        .filter(overlaps)
-        .count() as u32
-}
+    type Input = Vec<Assignment>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-fn main() {
-    println!("Part 1: {}", part1("data/Day04.txt"));
-    println!("Part 2: {}", part2("data/Day04.txt"));
+    fn parse(&self, input: &str) -> Result<Vec<Assignment>> {
+        parse_lines(input, parse_ranges)
+    }
+
+    fn part1(&self, assignments: &Vec<Assignment>) -> Result<u32> {
+        Ok(assignments.iter().filter(contained).count() as u32)
+    }
+
+    fn part2(&self, assignments: &Vec<Assignment>) -> Result<u32> {
+        Ok(assignments.iter().filter(overlaps).count() as u32)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
+
+    fn input() -> Vec<Assignment> {
+        Day04
+            .parse(&load("data/Day04_test.txt").expect("Couldn't open file"))
+            .unwrap()
+    }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("data/Day04_test.txt"), 2);
+        assert_eq!(Day04.part1(&input()).unwrap(), 2);
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("data/Day04_test.txt"), 4);
+        assert_eq!(Day04.part2(&input()).unwrap(), 4);
     }
 }
@@ -1,116 +1,130 @@
 use itertools::Itertools;
 use regex::Regex;
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
-fn parse_stack(s: &str, stacks: &mut Vec<VecDeque<char>>) {
+use crate::{
+    error::{AocError, Result},
+    Solution,
+};
+
+pub struct Day05;
+
+pub struct Crates {
+    stacks: Vec<VecDeque<char>>,
+    moves: Vec<(usize, usize, usize)>,
+}
+
+fn parse_stack(s: &str, stacks: &mut [VecDeque<char>]) -> Result<()> {
     let re = Regex::new("\\[([A-Z])\\]").unwrap();
-    let mut idx = 0;
-    for stack_top in s.chars().into_iter().chunks(4).into_iter() {
-        match re.captures(stack_top.collect::<String>().as_str()) {
-            Some(s) => stacks[idx].push_back(s[1].chars().nth(0).unwrap()),
-            None => (),
+    for (idx, stack_top) in s.chars().chunks(4).into_iter().enumerate() {
+        if let Some(cap) = re.captures(stack_top.collect::<String>().as_str()) {
+            stacks
+                .get_mut(idx)
+                .ok_or_else(|| AocError::parse(s, "a diagram row no wider than the first"))?
+                .push_back(cap[1].chars().next().unwrap())
         }
-        idx = idx + 1;
     }
+    Ok(())
 }
 
-fn parse_move(s: &str) -> Option<(usize, usize, usize)> {
-    let re = Regex::new("move ([0-9]+) from ([0-9]+) to ([0-9]+)").unwrap();
-
-    if let Some(cap) = re.captures(s) {
-        let a1 = cap[1].parse::<usize>().unwrap();
-        let a2 = cap[2].parse::<usize>().unwrap() - 1;
-        let a3 = cap[3].parse::<usize>().unwrap() - 1;
-        Some((a1, a2, a3))
-    } else {
-        None
-    }
+fn parse_move(s: &str, stacks: usize) -> Result<(usize, usize, usize)> {
+    let re = Regex::new("^move ([0-9]+) from ([0-9]+) to ([0-9]+)$").unwrap();
+    let error = || AocError::parse(s, "`move <n> from <stack> to <stack>`");
+    let cap = re.captures(s).ok_or_else(error)?;
+    let n = |i: usize| cap[i].parse::<usize>().map_err(|_| error());
+    let stack = |i: usize| match n(i)? {
+        x if x >= 1 && x <= stacks => Ok(x - 1),
+        _ => Err(error()),
+    };
+    Ok((n(1)?, stack(2)?, stack(3)?))
 }
 
-fn part1(name: &str) -> String {
-    let as_string = fs::read_to_string(name).expect("Couldn't open file");
-    let contents = as_string.split("\r\n").collect::<Vec<_>>();
-
-    let len = (contents[0].len() + 1) / 4;
-    let mut stacks: Vec<VecDeque<char>> = Vec::new();
-    for _i in 0..len {
-        stacks.push(VecDeque::new())
-    }
-    for line in contents.iter().take_while(|s| s.len() > 1) {
-        println!("{}", line);
-        if (&line).len() > 1 {
-            parse_stack(&line, &mut stacks)
-        }
-    }
+fn pop(stack: &mut VecDeque<char>, idx: usize) -> Result<char> {
+    stack
+        .pop_front()
+        .ok_or_else(|| AocError::solve(format!("stack {} is empty", idx + 1)))
+}
 
-    for (number, from, to) in contents
-        .into_iter()
-        .skip_while(|s| (*s).len() > 1)
-        .filter_map(parse_move)
-    {
-        for _i in 0..number {
-            let piece = stacks[from].pop_front().unwrap();
-            stacks[to].push_front(piece)
-        }
-    }
+fn tops(stacks: &mut [VecDeque<char>]) -> Result<String> {
     stacks
         .iter_mut()
-        .map(|s| s.pop_front().unwrap())
-        .collect::<String>()
+        .enumerate()
+        .map(|(idx, s)| pop(s, idx))
+        .collect()
 }
 
-fn part2(name: &str) -> String {
-    let as_string = fs::read_to_string(name).expect("Couldn't open file");
-    let contents = as_string.split("\r\n").collect::<Vec<_>>();
+impl Solution for Day05 {
+    const DAY: u32 = 5;
 
-    let len = (contents[0].len() + 1) / 4;
-    let mut stacks: Vec<VecDeque<char>> = Vec::new();
-    for _i in 0..len {
-        stacks.push(VecDeque::new())
-    }
-    for line in contents.iter().take_while(|s| s.len() > 1) {
-        println!("{}", line);
-        if (&line).len() > 1 {
-            parse_stack(&line, &mut stacks)
+    type Input = Crates;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Crates> {
+        let contents = input.lines().collect::<Vec<_>>();
+
+        let len = (contents.first().map_or(0, |s| s.len()) + 1) / 4;
+        let mut stacks: Vec<VecDeque<char>> = (0..len).map(|_| VecDeque::new()).collect();
+        let diagram = contents.iter().take_while(|s| s.len() > 1).count();
+        for (n, line) in contents.iter().take(diagram).enumerate() {
+            parse_stack(line, &mut stacks).map_err(|e| e.at_line(n + 1))?;
         }
+
+        // Moves start after the blank line following the diagram
+        let moves = contents
+            .iter()
+            .enumerate()
+            .skip(diagram + 1)
+            .map(|(n, s)| parse_move(s, len).map_err(|e| e.at_line(n + 1)))
+            .collect::<Result<_>>()?;
+        Ok(Crates { stacks, moves })
     }
 
-    for (number, from, to) in contents
-        .into_iter()
-        .skip_while(|s| (*s).len() > 1)
-        .filter_map(parse_move)
-    {
-        let mut tmp: VecDeque<char> = VecDeque::new();
-        for _i in 0..number {
-            let piece = stacks[from].pop_front().unwrap();
-            tmp.push_front(piece);
-        }
-        while let Some(c) = tmp.pop_front() {
-            stacks[to].push_front(c)
+    fn part1(&self, crates: &Crates) -> Result<String> {
+        let mut stacks = crates.stacks.clone();
+        for &(number, from, to) in &crates.moves {
+            for _i in 0..number {
+                let piece = pop(&mut stacks[from], from)?;
+                stacks[to].push_front(piece)
+            }
         }
+        tops(&mut stacks)
     }
-    stacks
-        .iter_mut()
-        .map(|s| s.pop_front().unwrap())
-        .collect::<String>()
-}
 
-fn main() {
-    println!("Part 1: {}", part1("data/Day05.txt"));
-    println!("Part 2: {}", part2("data/Day05.txt"));
+    fn part2(&self, crates: &Crates) -> Result<String> {
+        let mut stacks = crates.stacks.clone();
+        for &(number, from, to) in &crates.moves {
+            let mut tmp: VecDeque<char> = VecDeque::new();
+            for _i in 0..number {
+                let piece = pop(&mut stacks[from], from)?;
+                tmp.push_front(piece);
+            }
+            while let Some(c) = tmp.pop_front() {
+                stacks[to].push_front(c)
+            }
+        }
+        tops(&mut stacks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
+
+    fn input() -> Crates {
+        Day05
+            .parse(&load("data/Day05_test.txt").expect("Couldn't open file"))
+            .unwrap()
+    }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("data/Day05_test.txt"), "CMZ");
+        assert_eq!(Day05.part1(&input()).unwrap(), "CMZ");
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("data/Day05_test.txt"), "MCD");
+        assert_eq!(Day05.part2(&input()).unwrap(), "MCD");
     }
 }
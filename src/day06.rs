@@ -1,4 +1,11 @@
-use std::{collections::HashSet, fs};
+use std::collections::HashSet;
+
+use crate::{
+    error::{AocError, Result},
+    Solution,
+};
+
+pub struct Day06;
 
 fn not_unique(sl: &[char]) -> bool {
     let mut c: HashSet<char> = HashSet::new();
@@ -8,48 +15,52 @@ fn not_unique(sl: &[char]) -> bool {
     c.len() != sl.len()
 }
 
-fn part1(name: &str) -> usize {
-    let chars = fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .chars()
-        .collect::<Vec<char>>();
+fn marker_end(chars: &[char], size: usize) -> Result<usize> {
     chars
-        .windows(4)
-        .into_iter()
-        .take_while(|s| not_unique(&s))
-        .count()
-        + 4
+        .windows(size)
+        .position(|s| !not_unique(s))
+        .map(|p| p + size)
+        .ok_or_else(|| AocError::solve(format!("no run of {} distinct characters", size)))
 }
 
-fn part2(name: &str) -> usize {
-    let chars = fs::read_to_string(name)
-        .expect("Couldn't open file")
-        .chars()
-        .collect::<Vec<char>>();
-    chars
-        .windows(14)
-        .into_iter()
-        .take_while(|s| not_unique(&s))
-        .count()
-        + 14
-}
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    type Input = Vec<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<char>> {
+        Ok(input.chars().collect())
+    }
+
+    fn part1(&self, chars: &Vec<char>) -> Result<usize> {
+        marker_end(chars, 4)
+    }
 
-fn main() {
-    println!("Part 1: {}", part1("data/Day06.txt"));
-    println!("Part 2: {}", part2("data/Day06.txt"));
+    fn part2(&self, chars: &Vec<char>) -> Result<usize> {
+        marker_end(chars, 14)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
+
+    fn input() -> Vec<char> {
+        Day06
+            .parse(&load("data/Day06_test.txt").expect("Couldn't open file"))
+            .unwrap()
+    }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("data/Day06_test.txt"), 7);
+        assert_eq!(Day06.part1(&input()).unwrap(), 7);
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("data/Day06_test.txt"), 19);
+        assert_eq!(Day06.part2(&input()).unwrap(), 19);
     }
 }
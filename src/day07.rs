@@ -1,171 +1,1025 @@
-use std::{cmp::min, collections::HashMap, fs};
-
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{line_ending, one_of, space1},
-    combinator::{map, map_res, opt, recognize},
-    multi::{many0, many1},
-    sequence::{pair, preceded, separated_pair, terminated, tuple},
-    IResult,
+use std::{collections::HashMap, fmt::Write, fs, io, path::Path};
+
+use crate::{
+    error::{AocError, Result},
+    input::parse_lines,
+    Solution,
 };
 
+pub struct Day07;
+
+/// A directory as a tree: its files, with their sizes, and its
+/// subdirectories.
 #[derive(Debug, Eq, PartialEq)]
-struct Dir {
-    name: String,
-    file_sizes: Vec<usize>,
-    sub_dirs: Vec<Dir>,
+pub struct Dir {
+    pub name: String,
+    pub files: Vec<(String, usize)>,
+    pub sub_dirs: Vec<Dir>,
 }
 
-fn name(input: &str) -> IResult<&str, &str> {
-    recognize(many1(one_of(
-        "/abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789.",
-    )))(input)
+impl Dir {
+    /// The directory at `path` on disk, named `/`, with its subdirectories
+    /// and files in name order. Anything else, such as a symlink, is left
+    /// out. Names a transcript can't hold, with whitespace in them, are an
+    /// error.
+    pub fn read(path: &Path) -> Result<Dir> {
+        Dir::read_named(path, "/".to_string())
+    }
+
+    fn read_named(path: &Path, name: String) -> Result<Dir> {
+        let io_error = |path: &Path, source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut entries = fs::read_dir(path)
+            .and_then(|entries| entries.collect::<io::Result<Vec<_>>>())
+            .map_err(|e| io_error(path, e))?;
+        entries.sort_by_key(|e| e.file_name());
+        let mut dir = Dir {
+            name,
+            files: Vec::new(),
+            sub_dirs: Vec::new(),
+        };
+        for entry in entries {
+            let path = entry.path();
+            let name = match entry.file_name().into_string() {
+                Ok(name) if !name.contains(char::is_whitespace) => name,
+                _ => {
+                    let message = "a name without whitespace, to fit in a transcript";
+                    return Err(io_error(
+                        &path,
+                        io::Error::new(io::ErrorKind::InvalidData, message),
+                    ));
+                }
+            };
+            let metadata = fs::symlink_metadata(&path).map_err(|e| io_error(&path, e))?;
+            if metadata.is_dir() {
+                dir.sub_dirs.push(Dir::read_named(&path, name)?);
+            } else if metadata.is_file() {
+                dir.files.push((name, metadata.len() as usize));
+            }
+        }
+        Ok(dir)
+    }
+
+    /// A random tree of `dirs` directories, the root included, each with up
+    /// to four files. Files get smaller as the tree grows, so that like the
+    /// puzzle's it fills about 45000000 of the disk. The same `seed` always
+    /// gives the same tree.
+    pub fn synthetic(dirs: usize, seed: u64) -> Dir {
+        const EXTENSIONS: [&str; 4] = [".txt", ".dat", ".log", ".lst"];
+        let largest = (45_000_000 / dirs.max(1)).clamp(1, 300_000);
+        let mut rng = Rng::new(seed);
+        // Each directory's parent comes before it, so the tree can be put
+        // together from the end
+        let parents = (0..dirs).map(|i| rng.below(i)).collect::<Vec<_>>();
+        let mut tree = (0..dirs.max(1))
+            .map(|i| {
+                // Extensions keep file names apart from directory names
+                let files = (0..rng.below(5))
+                    .map(|j| {
+                        let name = letters(j) + EXTENSIONS[rng.below(EXTENSIONS.len())];
+                        (name, 1 + rng.below(largest))
+                    })
+                    .collect();
+                Dir {
+                    name: if i == 0 { "/".to_string() } else { letters(i) },
+                    files,
+                    sub_dirs: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+        for i in (1..tree.len()).rev() {
+            let dir = tree.pop().unwrap();
+            tree[parents[i]].sub_dirs.insert(0, dir);
+        }
+        tree.pop().unwrap()
+    }
 }
 
-fn integerp(input: &str) -> IResult<&str, usize> {
-    map_res(recognize(many1(one_of("0123456789"))), |s: &str| {
-        s.parse::<usize>()
-    })(input)
+/// `n` written in letters, as `a`, `b`, ..., `z`, `aa`, `ab` and so on:
+/// a name for the `n`th of something.
+fn letters(n: usize) -> String {
+    let mut name = Vec::new();
+    let mut n = n + 1;
+    while n > 0 {
+        n -= 1;
+        name.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
 }
 
-fn cd(input: &str) -> IResult<&str, &str> {
-    terminated(preceded(pair(tag("$ cd"), space1), name), opt(line_ending))(input)
+/// xorshift64*, enough to vary synthetic trees without a dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        // Mixed so nearby seeds start far apart; xorshift needs a state
+        // other than zero
+        let state = (seed ^ 0x9E37_79B9_7F4A_7C15).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        Rng(state.max(1))
+    }
+
+    /// A number in `0..n`, or 0 if `n` is 0.
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let random = self.0.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        (random >> 32) as usize % n.max(1)
+    }
 }
 
-fn cdup(input: &str) -> IResult<&str, ()> {
-    map(terminated(tag("$ cd .."), opt(line_ending)), |_| ())(input)
+/// A terminal session that explores `root`, in the puzzle's format: `$ ls`
+/// in each directory, listing its subdirectories then its files in order,
+/// then `$ cd` into each subdirectory in turn. Replaying it with
+/// `interpret` gives back `root`, as long as no directory has two entries
+/// with the same name.
+pub fn transcript(root: &Dir) -> String {
+    let mut out = "$ cd /\n".to_string();
+    list(&mut out, root);
+    out
 }
 
-fn dirls(input: &str) -> IResult<&str, (&str, Option<usize>)> {
-    map(
-        terminated(preceded(tag("dir "), name), opt(line_ending)),
-        |name| (name, None),
-    )(input)
+fn list(out: &mut String, dir: &Dir) {
+    out.push_str("$ ls\n");
+    for sub_dir in &dir.sub_dirs {
+        writeln!(out, "dir {}", sub_dir.name).unwrap();
+    }
+    for (name, size) in &dir.files {
+        writeln!(out, "{} {}", size, name).unwrap();
+    }
+    for sub_dir in &dir.sub_dirs {
+        writeln!(out, "$ cd {}", sub_dir.name).unwrap();
+        list(out, sub_dir);
+        out.push_str("$ cd ..\n");
+    }
 }
 
-fn filels(input: &str) -> IResult<&str, (&str, Option<usize>)> {
-    map(
-        terminated(separated_pair(integerp, space1, name), opt(line_ending)),
-        |(size, name)| (name, Some(size)),
-    )(input)
+/// An entry's index in a `Vfs`.
+pub type Id = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    File { size: usize },
+    Dir { children: Vec<Id> },
 }
 
-fn dir_entry(input: &str) -> IResult<&str, (&str, Option<usize>)> {
-    alt((dirls, filels))(input)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    /// From the root, as `/a/e/i`.
+    pub path: String,
+    pub parent: Option<Id>,
+    pub kind: Kind,
 }
 
-fn ls(input: &str) -> IResult<&str, HashMap<&str, usize>> {
-    let (rest, entries) = preceded(
-        terminated(tag("$ ls"), opt(line_ending)),
-        opt(many0(dir_entry)),
-    )(input)?;
-    Ok((
-        rest,
-        match entries {
-            None => HashMap::new(),
-            Some(x) => x
-                .into_iter()
-                .filter_map(|(r, s)| match s {
-                    None => None,
-                    Some(x) => Some((r, x)),
-                })
-                .collect(),
-        },
-    ))
+impl Entry {
+    pub fn is_dir(&self) -> bool {
+        matches!(self.kind, Kind::Dir { .. })
+    }
 }
 
-fn endit(input: &str) -> IResult<&str, ()> {
-    if input.len() == 0 {
-        Ok((input, ()))
-    } else {
-        map(cdup, |_| ())(input)
+/// A filesystem held in memory. Entries are stored in the order they were
+/// added, the root first, so every directory comes before what's in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vfs {
+    entries: Vec<Entry>,
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Vfs::new()
     }
 }
 
-fn filesystem(input: &str) -> IResult<&str, Dir> {
-    map(
-        tuple((cd, ls, terminated(many0(filesystem), endit))),
-        |(name, entries, dirs)| Dir {
+impl Vfs {
+    pub const ROOT: Id = 0;
+
+    /// An empty filesystem: just the root directory.
+    pub fn new() -> Vfs {
+        Vfs {
+            entries: vec![Entry {
+                name: "/".to_string(),
+                path: "/".to_string(),
+                parent: None,
+                kind: Kind::Dir {
+                    children: Vec::new(),
+                },
+            }],
+        }
+    }
+
+    pub fn get(&self, id: Id) -> &Entry {
+        &self.entries[id]
+    }
+
+    /// Every entry with its id, each directory before what's in it.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &Entry)> {
+        self.entries.iter().enumerate()
+    }
+
+    pub fn children(&self, dir: Id) -> &[Id] {
+        match &self.entries[dir].kind {
+            Kind::Dir { children } => children,
+            Kind::File { .. } => &[],
+        }
+    }
+
+    pub fn child(&self, dir: Id, name: &str) -> Option<Id> {
+        self.children(dir)
+            .iter()
+            .copied()
+            .find(|&c| self.entries[c].name == name)
+    }
+
+    /// The entry at an absolute path such as `/a/e`.
+    pub fn lookup(&self, path: &str) -> Option<Id> {
+        path.split('/')
+            .filter(|n| !n.is_empty())
+            .try_fold(Vfs::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    fn add(&mut self, dir: Id, name: &str, kind: Kind) -> std::result::Result<Id, String> {
+        let parent = &self.entries[dir];
+        if !parent.is_dir() {
+            return Err(self.conflict(dir));
+        }
+        let path = match parent.path.as_str() {
+            "/" => format!("/{}", name),
+            p => format!("{}/{}", p, name),
+        };
+        let id = self.entries.len();
+        self.entries.push(Entry {
             name: name.to_string(),
-            file_sizes: entries.values().map(|&s| s).collect(),
-            sub_dirs: dirs,
-        },
-    )(input)
+            path,
+            parent: Some(dir),
+            kind,
+        });
+        if let Kind::Dir { children } = &mut self.entries[dir].kind {
+            children.push(id);
+        }
+        Ok(id)
+    }
+
+    /// Describe what's already at `id`, to explain why it can't be replaced.
+    fn conflict(&self, id: Id) -> String {
+        match self.entries[id].kind {
+            Kind::File { size } => {
+                format!("`{}` is a file of size {}", self.entries[id].path, size)
+            }
+            Kind::Dir { .. } => format!("`{}` is a directory", self.entries[id].path),
+        }
+    }
+
+    /// Make the directory `name` in the directory `dir`, unless it's already
+    /// there. It's an error if a file has that name.
+    pub fn mkdir(&mut self, dir: Id, name: &str) -> std::result::Result<Id, String> {
+        match self.child(dir, name) {
+            Some(id) if self.entries[id].is_dir() => Ok(id),
+            Some(id) => Err(self.conflict(id)),
+            None => self.add(
+                dir,
+                name,
+                Kind::Dir {
+                    children: Vec::new(),
+                },
+            ),
+        }
+    }
+
+    /// Add the file `name` to the directory `dir`, unless it's already there
+    /// with the same size. It's an error if anything else has that name.
+    pub fn touch(&mut self, dir: Id, name: &str, size: usize) -> std::result::Result<Id, String> {
+        match self.child(dir, name) {
+            Some(id) if self.entries[id].kind == Kind::File { size } => Ok(id),
+            Some(id) => Err(self.conflict(id)),
+            None => self.add(dir, name, Kind::File { size }),
+        }
+    }
+
+    /// Copy `dir`'s contents into the directory `into`.
+    fn insert(&mut self, into: Id, dir: &Dir) -> std::result::Result<(), String> {
+        for (name, size) in &dir.files {
+            self.touch(into, name, *size)?;
+        }
+        for sub_dir in &dir.sub_dirs {
+            let id = self.mkdir(into, &sub_dir.name)?;
+            self.insert(id, sub_dir)?;
+        }
+        Ok(())
+    }
+
+    /// The filesystem `root` describes.
+    pub fn from_dir(root: &Dir) -> Result<Vfs> {
+        let mut vfs = Vfs::new();
+        vfs.insert(Vfs::ROOT, root).map_err(AocError::solve)?;
+        Ok(vfs)
+    }
+
+    /// The filesystem as a tree, with the root named `/`. Each directory's
+    /// files and subdirectories keep the order they were added in.
+    pub fn to_dir(&self) -> Dir {
+        self.dir_at(Vfs::ROOT)
+    }
+
+    fn dir_at(&self, id: Id) -> Dir {
+        let mut dir = Dir {
+            name: self.entries[id].name.clone(),
+            files: Vec::new(),
+            sub_dirs: Vec::new(),
+        };
+        for &child in self.children(id) {
+            match self.entries[child].kind {
+                Kind::File { size } => dir.files.push((self.entries[child].name.clone(), size)),
+                Kind::Dir { .. } => dir.sub_dirs.push(self.dir_at(child)),
+            }
+        }
+        dir
+    }
+
+    /// The total size of every entry, by id, as `du` would count it: a
+    /// file's own size, or everything in a directory.
+    pub fn du(&self) -> Vec<usize> {
+        let mut totals = vec![0; self.entries.len()];
+        // Children always come after their parents
+        for (id, entry) in self.entries.iter().enumerate().rev() {
+            if let Kind::File { size } = entry.kind {
+                totals[id] = size;
+            }
+            if let Some(parent) = entry.parent {
+                totals[parent] += totals[id];
+            }
+        }
+        totals
+    }
+
+    /// Every entry `pred` accepts, given the entry and its total size, with
+    /// that size.
+    pub fn find(&self, mut pred: impl FnMut(&Entry, usize) -> bool) -> Vec<(&Entry, usize)> {
+        self.entries
+            .iter()
+            .zip(self.du())
+            .filter(|&(e, size)| pred(e, size))
+            .collect()
+    }
+
+    /// The tree drawn the way the puzzle does, each directory's entries in
+    /// name order.
+    pub fn tree(&self) -> String {
+        let mut out = String::new();
+        self.draw(&mut out, Vfs::ROOT, 0);
+        out
+    }
+
+    fn draw(&self, out: &mut String, id: Id, depth: usize) {
+        let entry = &self.entries[id];
+        let indent = "  ".repeat(depth);
+        match entry.kind {
+            Kind::File { size } => {
+                writeln!(out, "{}- {} (file, size={})", indent, entry.name, size).unwrap()
+            }
+            Kind::Dir { .. } => {
+                writeln!(out, "{}- {} (dir)", indent, entry.name).unwrap();
+                let mut children = self.children(id).to_vec();
+                children.sort_by_key(|&c| &self.entries[c].name);
+                for child in children {
+                    self.draw(out, child, depth + 1);
+                }
+            }
+        }
+    }
+}
+
+/// Replays a terminal session to build the filesystem it explores. It
+/// reads one line at a time, either running a command or, after `$ ls`,
+/// taking in the listing, so navigation can be in any order: `cd /` from
+/// anywhere, the same directory listed twice, or a `cd` into a directory
+/// that hasn't been listed yet.
+struct Session {
+    vfs: Vfs,
+    cwd: Id,
+    listing: bool,
+}
+
+impl Session {
+    fn new() -> Session {
+        Session {
+            vfs: Vfs::new(),
+            cwd: Vfs::ROOT,
+            listing: false,
+        }
+    }
+
+    fn run(&mut self, line: &str) -> Result<()> {
+        let conflict = |e: String| {
+            AocError::parse(
+                line,
+                &format!("a line that agrees with what came before, but {}", e),
+            )
+        };
+        let words = line.split(' ').collect::<Vec<_>>();
+        match words[..] {
+            ["$", "cd", "/"] => self.cwd = Vfs::ROOT,
+            ["$", "cd", ".."] => {
+                self.cwd = self
+                    .vfs
+                    .get(self.cwd)
+                    .parent
+                    .ok_or_else(|| AocError::parse(line, "`$ cd ..` below the root"))?
+            }
+            ["$", "cd", name] => self.cwd = self.vfs.mkdir(self.cwd, name).map_err(conflict)?,
+            ["$", "ls"] => {}
+            ["dir", name] if self.listing => {
+                self.vfs.mkdir(self.cwd, name).map_err(conflict)?;
+            }
+            [size, name] if self.listing && size.parse::<usize>().is_ok() => {
+                let size = size.parse().unwrap();
+                self.vfs.touch(self.cwd, name, size).map_err(conflict)?;
+            }
+            _ => {
+                return Err(AocError::parse(
+                    line,
+                    if self.listing {
+                        "`$ cd <dir>`, `$ ls`, `dir <name>` or `<size> <name>`"
+                    } else {
+                        "`$ cd <dir>` or `$ ls`"
+                    },
+                ))
+            }
+        }
+        // Any command but `ls` ends a listing
+        if words[0] == "$" {
+            self.listing = line == "$ ls";
+        }
+        Ok(())
+    }
+}
+
+/// The filesystem a terminal session explores.
+pub fn interpret(log: &str) -> Result<Vfs> {
+    let mut session = Session::new();
+    parse_lines(log, |line| session.run(line))?;
+    Ok(session.vfs)
+}
+
+/// Plans what to delete to make room on a disk of size `disk` for something
+/// needing `required` free space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Planner {
+    pub disk: usize,
+    pub required: usize,
 }
 
-fn sizeof(prefix: &String, dir: &Dir) -> (usize, usize) {
-    let ftotal = dir.file_sizes.iter().map(|&s| s).sum::<usize>();
-    let name = prefix.to_owned() + "/" + dir.name.as_str();
-    let (dirtotal, diracc) = dir
-        .sub_dirs
-        .iter()
-        .map(|s| sizeof(&name, s))
-        .fold((0, 0), |(a, b), (c, d)| (a + c, b + d));
-    let total = ftotal + dirtotal;
-    let acc = diracc + if total < 100000 { total } else { 0 };
-    (total, acc)
+/// The puzzle's disk, and the space its update needs.
+impl Default for Planner {
+    fn default() -> Self {
+        Planner {
+            disk: 70_000_000,
+            required: 30_000_000,
+        }
+    }
 }
 
-fn smallest(a: Option<usize>, b: Option<usize>) -> Option<usize> {
-    match (a, b) {
-        (None, x) => x,
-        (x, None) => x,
-        (Some(x), Some(y)) => Some(min(x, y)),
+/// The directories from `dir` down, in preorder, each with the position
+/// just past everything inside it.
+fn preorder(vfs: &Vfs, dir: Id, out: &mut Vec<(Id, usize)>) {
+    let position = out.len();
+    out.push((dir, 0));
+    for &child in vfs.children(dir) {
+        if vfs.get(child).is_dir() {
+            preorder(vfs, child, out);
+        }
     }
+    out[position].1 = out.len();
 }
 
-fn smallest_deletable(prefix: &String, needed: usize, dir: &Dir) -> (usize, Option<usize>) {
-    let ftotal = dir.file_sizes.iter().map(|&s| s).sum::<usize>();
-    let name = prefix.to_owned() + "/" + dir.name.as_str();
-    let (dirtotal, diracc) = dir
-        .sub_dirs
-        .iter()
-        .map(|s| smallest_deletable(&name, needed, s))
-        .fold((0, None), |(a, b), (c, d)| (a + c, smallest(b, d)));
-    let total = ftotal + dirtotal;
-    let myacc = if total > needed { Some(total) } else { None };
-    let acc = smallest(myacc, diracc);
-    (total, acc)
+/// A set of totals below some width, a bit each. Only the first `used`
+/// words can have bits set, so work on a set can stop there.
+#[derive(Clone)]
+struct Totals {
+    width: usize,
+    words: Vec<u64>,
+    used: usize,
 }
 
-fn part1(input: &String) -> usize {
-    let (rest, result) = filesystem(input).unwrap();
-    println!("rest: {:?}", rest);
-    println!("result: {:?}", result);
-    sizeof(&"".to_string(), &result).1
+impl Totals {
+    fn new(width: usize) -> Totals {
+        Totals {
+            width,
+            words: vec![0; width.div_ceil(64)],
+            used: 0,
+        }
+    }
+
+    fn contains(&self, total: usize) -> bool {
+        total < self.width && self.words[total / 64] & (1 << (total % 64)) != 0
+    }
+
+    fn insert(&mut self, total: usize) {
+        if total < self.width {
+            self.words[total / 64] |= 1 << (total % 64);
+            self.used = self.used.max(total / 64 + 1);
+        }
+    }
+
+    fn union(&mut self, other: &Totals) {
+        let used = other.used.min(self.words.len());
+        for (a, b) in self.words[..used].iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+        self.used = self.used.max(used);
+    }
+
+    /// Every total in `self`, plus `n`, that's still below the width, put
+    /// in `out` in place of what was there.
+    fn plus_into(&self, n: usize, out: &mut Totals) {
+        let used = out.used.min(out.words.len());
+        out.words[..used].fill(0);
+        out.words.resize(self.words.len(), 0);
+        out.width = self.width;
+        let (words, bits) = (n / 64, n % 64);
+        // A carry can spill into the word past the last used
+        out.used = (self.used + words + 1).min(self.words.len());
+        for i in words..out.used {
+            let low = self.words.get(i - words).copied().unwrap_or(0);
+            let carried = match (bits, i - words) {
+                (0, _) | (_, 0) => 0,
+                (_, j) => self.words[j - 1] >> (64 - bits),
+            };
+            out.words[i] = low << bits | carried;
+        }
+        out.mask();
+    }
+
+    /// Clear any bits at or past the width.
+    fn mask(&mut self) {
+        if self.used == self.words.len() && !self.width.is_multiple_of(64) {
+            *self.words.last_mut().unwrap() &= (1 << (self.width % 64)) - 1;
+        }
+    }
+
+    /// Forget totals from `width` up.
+    fn narrow(&mut self, width: usize) {
+        if width < self.width {
+            self.width = width;
+            self.words.truncate(width.div_ceil(64));
+            self.used = self.used.min(self.words.len());
+            self.mask();
+        }
+    }
+
+    /// The smallest total that's at least `total`.
+    fn first_from(&self, total: usize) -> Option<usize> {
+        if total >= self.width {
+            return None;
+        }
+        let mut i = total / 64;
+        let mut word = self.words[i] & !0 << (total % 64);
+        while word == 0 {
+            i += 1;
+            word = *self.words.get(i)?;
+        }
+        Some(i * 64 + word.trailing_zeros() as usize)
+    }
 }
 
-fn part2(input: &String) -> usize {
-    let (rest, result) = filesystem(input).unwrap();
-    println!("rest: {:?}", rest);
-    println!("result: {:?}", result);
-    let prefix = "".to_string();
-    let needed = 30000000 - (70000000 - sizeof(&prefix, &result).0);
-    smallest_deletable(&prefix, needed, &result).1.unwrap()
+/// What a sweep does after each directory: carries on, carries on with only
+/// the totals below a new width, or stops there.
+enum Step {
+    Go,
+    Narrow(usize),
+    Stop,
 }
 
-fn main() {
-    let input = fs::read_to_string("data/Day07.txt").expect("Couldn't open file");
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+/// A knapsack over `dirs`, directories in preorder with the position past
+/// what's in each, where deleting one skips everything inside it. Works
+/// out the totals below `width` that deleting directories before `until`
+/// can free. What can be freed only grows along the order, so each
+/// deletion adds to what's possible from the end of its directory on.
+/// `step` sees each deletion's position and the totals it adds, and the
+/// position the sweep stops at, if it does, is returned.
+fn sweep(
+    dirs: &[(Id, usize)],
+    totals: &[usize],
+    until: usize,
+    mut width: usize,
+    mut step: impl FnMut(usize, &Totals) -> Step,
+) -> Option<usize> {
+    let mut possible = Totals::new(width);
+    possible.insert(0);
+    let mut taken = Totals::new(width);
+    let mut arriving: HashMap<usize, Totals> = HashMap::new();
+    // Sets done with, to reuse rather than allocate
+    let mut spare = Vec::new();
+    for (position, &(dir, end)) in dirs.iter().enumerate().take(until) {
+        if let Some(totals) = arriving.remove(&position) {
+            possible.union(&totals);
+            spare.push(totals);
+        }
+        // Deleting an empty directory never helps
+        if end > until || totals[dir] == 0 || totals[dir] >= width {
+            continue;
+        }
+        possible.plus_into(totals[dir], &mut taken);
+        match step(position, &taken) {
+            Step::Go => {}
+            Step::Narrow(narrower) => {
+                width = narrower;
+                for totals in [&mut possible, &mut taken]
+                    .into_iter()
+                    .chain(arriving.values_mut())
+                {
+                    totals.narrow(width);
+                }
+            }
+            Step::Stop => return Some(position),
+        }
+        match arriving.get_mut(&end) {
+            Some(totals) => totals.union(&taken),
+            None => {
+                let next = spare.pop().unwrap_or_else(|| Totals::new(width));
+                arriving.insert(end, std::mem::replace(&mut taken, next));
+            }
+        }
+    }
+    None
+}
+
+/// Directories to delete, with the space that frees.
+pub type Plan<'a> = (usize, Vec<&'a Entry>);
+
+impl Planner {
+    /// How much more space needs freeing on a disk holding `vfs`.
+    pub fn needed(&self, vfs: &Vfs) -> Result<usize> {
+        let free = self
+            .disk
+            .checked_sub(vfs.du()[Vfs::ROOT])
+            .ok_or_else(|| AocError::solve("the files don't fit on the disk"))?;
+        Ok(self.required.saturating_sub(free))
+    }
+
+    /// Every directory big enough to free the space needed on its own,
+    /// smallest first.
+    pub fn candidates<'a>(&self, vfs: &'a Vfs) -> Result<Vec<(&'a Entry, usize)>> {
+        let needed = self.needed(vfs)?;
+        let mut candidates = vfs.find(|e, size| e.is_dir() && size >= needed);
+        candidates.sort_by_key(|&(e, size)| (size, &e.path));
+        Ok(candidates)
+    }
+
+    /// The smallest directory that frees enough on its own.
+    pub fn smallest_deletable<'a>(&self, vfs: &'a Vfs) -> Result<(&'a Entry, usize)> {
+        self.candidates(vfs)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AocError::solve("no directory is big enough to delete"))
+    }
+
+    /// The directories, none inside another, that between them free enough
+    /// while deleting as little as possible.
+    pub fn minimal_set<'a>(&self, vfs: &'a Vfs) -> Result<Plan<'a>> {
+        let needed = self.needed(vfs)?;
+        if needed == 0 {
+            return Ok((0, Vec::new()));
+        }
+        let totals = vfs.du();
+        let (smallest_dir, smallest) = self.smallest_deletable(vfs)?;
+        let mut dirs = Vec::new();
+        preorder(vfs, Vfs::ROOT, &mut dirs);
+
+        // Only totals below the best found so far could do better, and
+        // nothing beats freeing exactly what's needed
+        let (mut best, mut found) = (smallest, None);
+        sweep(&dirs, &totals, dirs.len(), smallest, |position, taken| {
+            let Some(total) = taken.first_from(needed) else {
+                return Step::Go;
+            };
+            (best, found) = (total, Some(position));
+            if total == needed {
+                Step::Stop
+            } else {
+                Step::Narrow(total)
+            }
+        });
+        let Some(mut position) = found else {
+            return Ok((smallest, vec![smallest_dir]));
+        };
+        // Work back from the best total: the directory whose deletion first
+        // made it possible, then the one that first made what's left
+        // possible before that, and so on
+        let mut chosen = Vec::new();
+        let mut freed = best;
+        loop {
+            let dir = dirs[position].0;
+            chosen.push(vfs.get(dir));
+            freed -= totals[dir];
+            if freed == 0 {
+                break;
+            }
+            let reaches = |_, taken: &Totals| {
+                if taken.contains(freed) {
+                    Step::Stop
+                } else {
+                    Step::Go
+                }
+            };
+            position = sweep(&dirs, &totals, position, freed + 1, reaches).unwrap();
+        }
+        chosen.sort_by_key(|e| &e.path);
+        Ok((best, chosen))
+    }
+
+    /// The space needed, the `n` smallest directories that free it on their
+    /// own, and the smallest set that does.
+    pub fn report(&self, vfs: &Vfs, n: usize) -> Result<String> {
+        let used = vfs.du()[Vfs::ROOT];
+        let mut out = format!(
+            "{} of {} used, {} free; {} needed, so {} to free\n",
+            used,
+            self.disk,
+            self.disk.saturating_sub(used),
+            self.required,
+            self.needed(vfs)?
+        );
+        out.push_str("Smallest directories freeing enough alone:\n");
+        for (entry, size) in self.candidates(vfs)?.into_iter().take(n) {
+            writeln!(out, "{:>10}  {}", size, entry.path).unwrap();
+        }
+        let (freed, dirs) = self.minimal_set(vfs)?;
+        writeln!(out, "Smallest set freeing enough: {}", freed).unwrap();
+        for entry in dirs {
+            writeln!(
+                out,
+                "{:>10}  {}",
+                vfs.du()[vfs.lookup(&entry.path).unwrap()],
+                entry.path
+            )
+            .unwrap();
+        }
+        Ok(out)
+    }
+}
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = Vfs;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vfs> {
+        interpret(input)
+    }
+
+    fn part1(&self, vfs: &Vfs) -> Result<usize> {
+        Ok(vfs
+            .find(|e, size| e.is_dir() && size <= 100_000)
+            .iter()
+            .map(|(_, size)| size)
+            .sum())
+    }
+
+    fn part2(&self, vfs: &Vfs) -> Result<usize> {
+        Planner::default()
+            .smallest_deletable(vfs)
+            .map(|(_, size)| size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::load;
 
-    fn input() -> String {
-        fs::read_to_string("data/Day07_test.txt").expect("Couldn't open file")
+    fn input() -> Vfs {
+        Day07
+            .parse(&load("data/Day07_test.txt").expect("Couldn't open file"))
+            .unwrap()
     }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(&input()), 95437);
+        assert_eq!(Day07.part1(&input()).unwrap(), 95437);
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(&input()), 24933642);
+        assert_eq!(Day07.part2(&input()).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn planner_works() {
+        let vfs = input();
+        let planner = Planner::default();
+        assert_eq!(planner.needed(&vfs).unwrap(), 8381165);
+        let paths =
+            |entries: Vec<&Entry>| entries.iter().map(|e| e.path.clone()).collect::<Vec<_>>();
+        let candidates = planner.candidates(&vfs).unwrap();
+        assert_eq!(
+            paths(candidates.iter().map(|(e, _)| *e).collect()),
+            ["/d", "/"]
+        );
+        let (freed, dirs) = planner.minimal_set(&vfs).unwrap();
+        assert_eq!((freed, paths(dirs)), (24933642, vec!["/d".to_string()]));
+        assert!(planner
+            .report(&vfs, 1)
+            .unwrap()
+            .starts_with("48381165 of 70000000 used, 21618835 free; 30000000 needed, so 8381165 to free\nSmallest directories freeing enough alone:\n  24933642  /d\nSmallest set"));
+
+        // Two small directories beat the one big enough on its own
+        let mut vfs = Vfs::new();
+        for (parent, name, size) in [
+            ("/", "a", 4),
+            ("/", "b", 6),
+            ("/", "c", 0),
+            ("/c", "c1", 5),
+            ("/c", "c2", 5),
+        ] {
+            let parent = vfs.lookup(parent).unwrap();
+            let dir = vfs.mkdir(parent, name).unwrap();
+            vfs.touch(dir, "f", size).unwrap();
+        }
+        // Empty directories free nothing, so never belong in a plan
+        vfs.mkdir(Vfs::ROOT, "empty").unwrap();
+        let planner = Planner {
+            disk: 31,
+            required: 20,
+        };
+        assert_eq!(planner.smallest_deletable(&vfs).unwrap().1, 10);
+        let (freed, dirs) = planner.minimal_set(&vfs).unwrap();
+        assert_eq!(
+            (freed, paths(dirs)),
+            (9, vec!["/a".to_string(), "/c/c1".to_string()])
+        );
+        let roomy = Planner {
+            disk: 100,
+            required: 20,
+        };
+        assert_eq!(roomy.minimal_set(&vfs).unwrap(), (0, vec![]));
+
+        let vfs = Vfs::from_dir(&Dir::synthetic(500, 1)).unwrap();
+        let (freed, dirs) = Planner::default().minimal_set(&vfs).unwrap();
+        let du = vfs.du();
+        let size = |e: &Entry| du[vfs.lookup(&e.path).unwrap()];
+        assert_eq!(dirs.iter().map(|e| size(e)).sum::<usize>(), freed);
+        assert!(freed >= Planner::default().needed(&vfs).unwrap());
+        assert!(dirs.iter().all(|e| size(e) > 0));
+    }
+
+    #[test]
+    fn vfs_works() {
+        let vfs = input();
+        let e = vfs.lookup("/a/e").unwrap();
+        assert_eq!(vfs.get(e).path, "/a/e");
+        assert_eq!(
+            vfs.get(vfs.lookup("/d/d.log").unwrap()).kind,
+            Kind::File { size: 8033020 }
+        );
+        assert_eq!(vfs.lookup("/a/x"), None);
+        assert_eq!(vfs.du()[e], 584);
+        assert_eq!(vfs.du()[Vfs::ROOT], 48381165);
+
+        let big_files = vfs.find(|e, size| !e.is_dir() && size > 8_000_000);
+        let names = big_files
+            .iter()
+            .map(|(e, _)| e.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["b.txt", "c.dat", "d.log"]);
+        assert_eq!(vfs.find(|e, _| e.name.ends_with(".lst")).len(), 1);
+
+        assert_eq!(
+            vfs.tree(),
+            "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+"
+        );
+    }
+
+    #[test]
+    fn interpret_works() {
+        let vfs = interpret(
+            "$ cd a
+$ ls
+10 f
+$ cd /
+$ ls
+dir a
+5 g
+$ ls
+5 g
+$ cd a
+$ cd b
+$ ls
+1 h",
+        )
+        .unwrap();
+        assert_eq!(
+            vfs.tree(),
+            "- / (dir)
+  - a (dir)
+    - b (dir)
+      - h (file, size=1)
+    - f (file, size=10)
+  - g (file, size=5)
+"
+        );
+        let error = |log: &str| interpret(log).unwrap_err().to_string();
+        assert_eq!(
+            error("$ ls\n5 g\n$ ls\n6 g"),
+            "input:4: expected a line that agrees with what came before, \
+             but `/g` is a file of size 5, found \"6 g\""
+        );
+        assert_eq!(
+            error("$ ls\n5 g\n$ cd g"),
+            "input:3: expected a line that agrees with what came before, \
+             but `/g` is a file of size 5, found \"$ cd g\""
+        );
+        assert!(error("1 f").starts_with("input:1: expected `$ cd <dir>` or `$ ls`"));
+        assert!(error("$ cd a\n$ cd ..\n$ cd ..").starts_with("input:3:"));
+        assert!(error("$ ls\ndir a\n$ pwd").starts_with("input:3:"));
+    }
+
+    #[test]
+    fn conflicts_are_errors() {
+        let mut vfs = Vfs::new();
+        let a = vfs.mkdir(Vfs::ROOT, "a").unwrap();
+        assert_eq!(vfs.mkdir(Vfs::ROOT, "a").unwrap(), a);
+        vfs.touch(a, "f", 10).unwrap();
+        assert!(vfs.touch(a, "f", 10).is_ok());
+        assert!(vfs.touch(a, "f", 11).is_err());
+        assert!(vfs.mkdir(a, "f").is_err());
+        assert!(vfs.touch(Vfs::ROOT, "a", 1).is_err());
+    }
+
+    #[test]
+    fn transcripts_round_trip() {
+        let dir = Dir {
+            name: "/".to_string(),
+            files: vec![("b.txt".to_string(), 14848514)],
+            sub_dirs: vec![Dir {
+                name: "a".to_string(),
+                files: vec![("f".to_string(), 29116)],
+                sub_dirs: Vec::new(),
+            }],
+        };
+        assert_eq!(
+            transcript(&dir),
+            "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\n29116 f\n$ cd ..\n"
+        );
+        assert_eq!(interpret(&transcript(&dir)).unwrap().to_dir(), dir);
+
+        let example = input().to_dir();
+        let replayed = interpret(&transcript(&example)).unwrap();
+        assert_eq!(replayed.to_dir(), example);
+        assert_eq!(replayed.tree(), input().tree());
+
+        for seed in 0..50 {
+            let dir = Dir::synthetic(1 + seed as usize * 4, seed);
+            let vfs = interpret(&transcript(&dir)).unwrap();
+            assert_eq!(vfs.to_dir(), dir, "seed {}", seed);
+            assert_eq!(
+                vfs.du()[Vfs::ROOT],
+                Vfs::from_dir(&dir).unwrap().du()[Vfs::ROOT]
+            );
+        }
+        assert_eq!(Dir::synthetic(30, 7), Dir::synthetic(30, 7));
+        assert_ne!(Dir::synthetic(30, 7), Dir::synthetic(30, 8));
+    }
+
+    #[test]
+    fn real_directories_can_be_read() {
+        let root = std::env::temp_dir().join(format!("aoc-day07-{}", std::process::id()));
+        fs::create_dir_all(root.join("a/e")).unwrap();
+        fs::write(root.join("b.txt"), "hello").unwrap();
+        fs::write(root.join("a/e/i"), "").unwrap();
+        let dir = Dir::read(&root);
+        fs::write(root.join("a b"), "").unwrap();
+        let spaced = Dir::read(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        let dir = dir.unwrap();
+        let vfs = interpret(&transcript(&dir)).unwrap();
+        assert_eq!(vfs.to_dir(), dir);
+        assert_eq!(vfs.du()[Vfs::ROOT], 5);
+        assert!(vfs.lookup("/a/e/i").is_some());
+        assert!(spaced
+            .unwrap_err()
+            .to_string()
+            .contains("without whitespace"));
     }
 }
@@ -1,37 +1,34 @@
-use itertools::Itertools;
-use regex::Regex;
 use std::str::FromStr;
 
-const INPUT: &str = include_str!("../data/Day10.txt");
+use crate::{
+    error::{AocError, Result},
+    grid::Grid,
+    input::parse_lines,
+    Solution,
+};
 
-#[derive(Debug)]
-struct ParseError {
-    _o: String,
-}
+pub struct Day10;
 
-enum Instruction {
+pub enum Instruction {
     NoOp,
     AddX(i32),
 }
 
 impl FromStr for Instruction {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(" ").take(2).collect::<Vec<_>>();
-        if parts[0] == "addx" {
-            let size = parts[1]
-                .parse::<i32>()
-                .or(Err(ParseError { _o: s.to_string() }))?;
-            Ok(Instruction::AddX(size))
-        } else {
-            Ok(Instruction::NoOp)
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let error = || AocError::parse(s, "`noop` or `addx <n>`");
+        match s.split(' ').collect::<Vec<_>>()[..] {
+            ["noop"] => Ok(Instruction::NoOp),
+            ["addx", size] => Ok(Instruction::AddX(size.parse::<i32>().map_err(|_| error())?)),
+            _ => Err(error()),
         }
     }
 }
 
 impl Instruction {
-    fn steps(self: &Self) -> Vec<(i32, String)> {
+    fn steps(&self) -> Vec<(i32, String)> {
         match self {
             Instruction::NoOp => vec![(0, "noop".to_string())],
             Instruction::AddX(x) => vec![(0, "addx1".to_string()), (*x, "addx2".to_string())],
@@ -39,73 +36,63 @@ impl Instruction {
     }
 }
 
-fn part1(input: &str) -> i32 {
+fn cycles(instructions: &[Instruction]) -> Vec<(usize, i32, i32, String)> {
     let mut acc: Vec<(usize, i32, i32, String)> = Vec::new();
     let mut curr: i32 = 1;
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    for (idx, (change, label)) in eol
-        .split(input)
-        .map(|s| s.parse::<Instruction>().unwrap())
-        .flat_map(|s| s.steps().into_iter())
-        .enumerate()
-    {
+    for (idx, (change, label)) in instructions.iter().flat_map(|s| s.steps()).enumerate() {
         let s = idx + 1;
         acc.push((s, change, curr, label));
         curr += change;
     }
     acc.push((acc.len() + 1, 0, curr, "endstop".to_string()));
+    acc
+}
 
-    let steps = acc
-        .into_iter()
-        .take(221)
-        .filter(|(idx, _, _, _)| idx % 40 == 20)
-        .collect::<Vec<_>>();
+impl Solution for Day10 {
+    const DAY: u32 = 10;
 
-    steps.into_iter().map(|s| (s.0 as i32) * s.2).sum::<i32>()
-}
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = String;
 
-fn part2(input: &str) -> String {
-    let mut acc: Vec<(usize, i32, i32, String)> = Vec::new();
-    let mut curr: i32 = 1;
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    for (idx, (change, label)) in eol
-        .split(input)
-        .map(|s| s.parse::<Instruction>().unwrap())
-        .flat_map(|s| s.steps().into_iter())
-        .enumerate()
-    {
-        let s = idx + 1;
-        acc.push((s, change, curr, label));
-        curr += change;
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>> {
+        parse_lines(input, |s| s.parse::<Instruction>())
     }
-    acc.push((acc.len() + 1, 0, curr, "endstop".to_string()));
 
-    let mut screen = [['.'; 40]; 6];
+    fn part1(&self, instructions: &Vec<Instruction>) -> Result<i32> {
+        let steps = cycles(instructions)
+            .into_iter()
+            .take(221)
+            .filter(|(idx, _, _, _)| idx % 40 == 20)
+            .collect::<Vec<_>>();
 
-    for y in 0..6 {
-        for x in 0..40 {
-            let idx = y * 40 + x;
-            let crsr_pos = acc[idx].2;
-            if crsr_pos >= (x as i32) - 1 && crsr_pos <= (x as i32) + 1 {
-                screen[y][x] = '#';
-            }
-        }
+        Ok(steps.into_iter().map(|s| (s.0 as i32) * s.2).sum::<i32>())
     }
 
-    screen
-        .iter()
-        .map(|s| s.iter().collect::<String>())
-        .join("\n")
-}
+    fn part2(&self, instructions: &Vec<Instruction>) -> Result<String> {
+        let acc = cycles(instructions);
+        if acc.len() < 240 {
+            return Err(AocError::solve(
+                "the program stops before the screen is drawn",
+            ));
+        }
+        let screen = Grid::new(40, 6, '.').map_with_pos(|(x, y), &pixel| {
+            let crsr_pos = acc[y * 40 + x].2;
+            if crsr_pos >= (x as i32) - 1 && crsr_pos <= (x as i32) + 1 {
+                '#'
+            } else {
+                pixel
+            }
+        });
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2:\r\n{}", part2(INPUT));
+        Ok(screen.to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day10_test.txt");
 
@@ -118,11 +105,21 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 13140);
+        assert_eq!(
+            Day10
+                .part1(&Day10.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            13140
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), TEST_PART2_EXPECTED.to_string());
+        assert_eq!(
+            Day10
+                .part2(&Day10.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            TEST_PART2_EXPECTED.to_string()
+        );
     }
 }
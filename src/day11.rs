@@ -9,18 +9,24 @@ use nom::{
     IResult,
 };
 
-const INPUT: &str = include_str!("../data/Day11.txt");
+use crate::{
+    error::{AocError, Result},
+    input::parse_all,
+    Solution,
+};
+
+pub struct Day11;
 
 #[derive(Debug, PartialEq, Eq)]
 enum Operand {
-    OLD,
-    NUM(usize),
+    Old,
+    Num(usize),
 }
 
 #[derive(Debug, PartialEq, Eq)]
 enum Operator {
-    PLUS,
-    MUL,
+    Plus,
+    Mul,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -30,7 +36,7 @@ enum Operation {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Monkey {
+pub struct Monkey {
     idx: usize,
     starting_items: Vec<usize>,
     op: Operation,
@@ -39,6 +45,18 @@ struct Monkey {
     onfalse: usize,
 }
 
+impl Operation {
+    fn apply(&self, w: usize) -> Option<usize> {
+        match self {
+            Operation::Plus(Operand::Old, Operand::Num(x)) => Some(w + x),
+            Operation::Plus(Operand::Old, Operand::Old) => Some(w + w),
+            Operation::Mul(Operand::Old, Operand::Num(x)) => Some(w * x),
+            Operation::Mul(Operand::Old, Operand::Old) => Some(w * w),
+            _ => None,
+        }
+    }
+}
+
 fn integerp(input: &str) -> IResult<&str, usize> {
     map_res(recognize(many1(one_of("0123456789"))), |s: &str| {
         s.parse::<usize>()
@@ -49,8 +67,8 @@ fn operand(input: &str) -> IResult<&str, Operand> {
     preceded(
         space0,
         alt((
-            map(tag("old"), |_| Operand::OLD),
-            map(integerp, |n| Operand::NUM(n)),
+            map(tag("old"), |_| Operand::Old),
+            map(integerp, Operand::Num),
         )),
     )(input)
 }
@@ -59,8 +77,8 @@ fn operator(input: &str) -> IResult<&str, Operator> {
     preceded(
         space0,
         alt((
-            map(tag("+"), |_| Operator::PLUS),
-            map(tag("*"), |_| Operator::MUL),
+            map(tag("+"), |_| Operator::Plus),
+            map(tag("*"), |_| Operator::Mul),
         )),
     )(input)
 }
@@ -69,8 +87,8 @@ fn operation(input: &str) -> IResult<&str, Operation> {
     map(
         tuple((operand, operator, operand)),
         |(left, op, right)| match op {
-            Operator::PLUS => Operation::Plus(left, right),
-            Operator::MUL => Operation::Mul(left, right),
+            Operator::Plus => Operation::Plus(left, right),
+            Operator::Mul => Operation::Mul(left, right),
         },
     )(input)
 }
@@ -146,101 +164,108 @@ fn monkey(input: &str) -> IResult<&str, Monkey> {
     )(input)
 }
 
-fn part1(input: &str) -> usize {
-    let monkeys = separated_list1(line_ending, monkey)(input).unwrap().1;
-
-    let mut monkey_items = monkeys
-        .iter()
-        .map(|m| m.starting_items.clone())
-        .collect::<Vec<_>>();
-    let mut monkey_counts: Vec<usize> = monkeys.iter().map(|_| 0).collect();
-
-    for _round in 0..20 {
-        for idx in 0..monkeys.len() {
-            let items = monkey_items[idx].clone();
-            // this is how many times I will inspect an item
-            monkey_counts[idx] += items.len();
-            monkey_items[idx].clear();
-            // work out the disposition of each item
-            for w in items {
-                let v = match monkeys[idx].op {
-                    Operation::Plus(Operand::OLD, Operand::NUM(x)) => w + x,
-                    Operation::Plus(Operand::OLD, Operand::OLD) => w + w,
-                    Operation::Mul(Operand::OLD, Operand::NUM(x)) => w * x,
-                    Operation::Mul(Operand::OLD, Operand::OLD) => w * w,
-                    _ => panic!("Should Never Happen"),
-                };
-                let v = v / 3;
-                let pass_to = if v % monkeys[idx].modulo == 0 {
-                    monkeys[idx].ontrue
-                } else {
-                    monkeys[idx].onfalse
-                };
-                monkey_items[pass_to].push(v);
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Monkey>> {
+        let monkeys = parse_all(
+            input,
+            "a monkey description",
+            separated_list1(line_ending, monkey)(input),
+        )?;
+        for m in &monkeys {
+            for target in [m.ontrue, m.onfalse] {
+                if target >= monkeys.len() {
+                    return Err(AocError::parse(
+                        &format!("throw to monkey {}", target),
+                        "a monkey that exists",
+                    ));
+                }
             }
         }
-        println!("Monkey Counts({}) = {:?}", _round, monkey_counts);
-        for idx in 0..monkeys.len() {
-            println!(">> Monkey {} Has: {:?}", idx, monkey_items[idx]);
-        }
+        Ok(monkeys)
     }
-    monkey_counts
-        .into_iter()
-        .sorted_by(|a, b| b.cmp(a))
-        .take(2)
-        .product()
-}
 
-fn part2(input: &str) -> usize {
-    let monkeys = separated_list1(line_ending, monkey)(input).unwrap().1;
-
-    let mut monkey_items = monkeys
-        .iter()
-        .map(|m| m.starting_items.clone())
-        .collect::<Vec<_>>();
-    let mut monkey_counts: Vec<usize> = monkeys.iter().map(|_| 0).collect();
-
-    let modulo = monkeys.iter().map(|s| s.modulo).product::<usize>();
-
-    for _round in 0..10000 {
-        for idx in 0..monkeys.len() {
-            let items = monkey_items[idx].clone();
-            // this is how many times I will inspect an item
-            monkey_counts[idx] += items.len();
-            monkey_items[idx].clear();
-            // work out the disposition of each item
-            for w in items {
-                let v = match monkeys[idx].op {
-                    Operation::Plus(Operand::OLD, Operand::NUM(x)) => w + x,
-                    Operation::Plus(Operand::OLD, Operand::OLD) => w + w,
-                    Operation::Mul(Operand::OLD, Operand::NUM(x)) => w * x,
-                    Operation::Mul(Operand::OLD, Operand::OLD) => w * w,
-                    _ => panic!("Should Never Happen"),
-                };
-                let pass_to = if v % monkeys[idx].modulo == 0 {
-                    monkeys[idx].ontrue
-                } else {
-                    monkeys[idx].onfalse
-                };
-                monkey_items[pass_to].push(v % modulo);
+    fn part1(&self, monkeys: &Vec<Monkey>) -> Result<usize> {
+        let mut monkey_items = monkeys
+            .iter()
+            .map(|m| m.starting_items.clone())
+            .collect::<Vec<_>>();
+        let mut monkey_counts: Vec<usize> = monkeys.iter().map(|_| 0).collect();
+
+        for _round in 0..20 {
+            for idx in 0..monkeys.len() {
+                let items = monkey_items[idx].clone();
+                // this is how many times I will inspect an item
+                monkey_counts[idx] += items.len();
+                monkey_items[idx].clear();
+                // work out the disposition of each item
+                for w in items {
+                    let v = monkeys[idx].op.apply(w).ok_or_else(|| {
+                        AocError::solve(format!("monkey {} has an unsupported operation", idx))
+                    })?;
+                    let v = v / 3;
+                    let pass_to = if v % monkeys[idx].modulo == 0 {
+                        monkeys[idx].ontrue
+                    } else {
+                        monkeys[idx].onfalse
+                    };
+                    monkey_items[pass_to].push(v);
+                }
             }
         }
+        Ok(monkey_counts
+            .into_iter()
+            .sorted_by(|a, b| b.cmp(a))
+            .take(2)
+            .product())
     }
-    monkey_counts
-        .into_iter()
-        .sorted_by(|a, b| b.cmp(a))
-        .take(2)
-        .product()
-}
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+    fn part2(&self, monkeys: &Vec<Monkey>) -> Result<usize> {
+        let mut monkey_items = monkeys
+            .iter()
+            .map(|m| m.starting_items.clone())
+            .collect::<Vec<_>>();
+        let mut monkey_counts: Vec<usize> = monkeys.iter().map(|_| 0).collect();
+
+        let modulo = monkeys.iter().map(|s| s.modulo).product::<usize>();
+
+        for _round in 0..10000 {
+            for idx in 0..monkeys.len() {
+                let items = monkey_items[idx].clone();
+                // this is how many times I will inspect an item
+                monkey_counts[idx] += items.len();
+                monkey_items[idx].clear();
+                // work out the disposition of each item
+                for w in items {
+                    let v = monkeys[idx].op.apply(w).ok_or_else(|| {
+                        AocError::solve(format!("monkey {} has an unsupported operation", idx))
+                    })?;
+                    let pass_to = if v % monkeys[idx].modulo == 0 {
+                        monkeys[idx].ontrue
+                    } else {
+                        monkeys[idx].onfalse
+                    };
+                    monkey_items[pass_to].push(v % modulo);
+                }
+            }
+        }
+        Ok(monkey_counts
+            .into_iter()
+            .sorted_by(|a, b| b.cmp(a))
+            .take(2)
+            .product())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day11_test.txt");
 
@@ -259,15 +284,15 @@ mod tests {
 
     #[test]
     fn operand_works() {
-        assert_eq!(operand(" old\r\n").unwrap(), ("\r\n", Operand::OLD));
-        assert_eq!(operand("19\r\n").unwrap(), ("\r\n", Operand::NUM(19)));
+        assert_eq!(operand(" old\r\n").unwrap(), ("\r\n", Operand::Old));
+        assert_eq!(operand("19\r\n").unwrap(), ("\r\n", Operand::Num(19)));
     }
 
     #[test]
     fn operation_works() {
         assert_eq!(
             operation(" old + 19\r\n").unwrap(),
-            ("\r\n", Operation::Plus(Operand::OLD, Operand::NUM(19)))
+            ("\r\n", Operation::Plus(Operand::Old, Operand::Num(19)))
         );
     }
 
@@ -275,7 +300,7 @@ mod tests {
     fn operation_line_add_works() {
         assert_eq!(
             operation_line("  Operation: new = old + 19\r\n").unwrap(),
-            ("", Operation::Plus(Operand::OLD, Operand::NUM(19)))
+            ("", Operation::Plus(Operand::Old, Operand::Num(19)))
         );
     }
 
@@ -283,7 +308,7 @@ mod tests {
     fn operation_line_mul_works() {
         assert_eq!(
             operation_line("  Operation: new = 19 * old\r\n").unwrap(),
-            ("", Operation::Mul(Operand::NUM(19), Operand::OLD))
+            ("", Operation::Mul(Operand::Num(19), Operand::Old))
         );
     }
 
@@ -310,11 +335,21 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 10605);
+        assert_eq!(
+            Day11
+                .part1(&Day11.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            10605
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 2713310158);
+        assert_eq!(
+            Day11
+                .part2(&Day11.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            2713310158
+        );
     }
 }
@@ -1,9 +1,79 @@
-use std::cmp::Reverse;
+use crate::{
+    error::{AocError, Result},
+    geometry::Point2,
+    grid::{Grid, Pos, ALL_DIRECTIONS, ORTHOGONAL},
+    render::{self, Rgb},
+    search::{self, Reached, Route},
+    Solution,
+};
 
-use priority_queue::PriorityQueue;
-use regex::Regex;
+/// How you're allowed to move over the heightmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Climbing {
+    /// The most a step can climb.
+    pub max_ascent: i32,
+    /// The most a step can drop, if there's a limit.
+    pub max_descent: Option<i32>,
+    /// Whether steps can be diagonal as well as along rows and columns.
+    pub diagonals: bool,
+    /// What each unit of altitude gained or lost costs, on top of the step.
+    /// It can't be negative, so every step costs at least one and the
+    /// searches stay correct, and routes are costed in `u64` so even the
+    /// largest can't overflow.
+    pub cost_per_change: u32,
+}
+
+/// The puzzle's rules: climb at most one, drop any distance, no diagonals,
+/// and every step costs one.
+impl Default for Climbing {
+    fn default() -> Self {
+        Climbing {
+            max_ascent: 1,
+            max_descent: None,
+            diagonals: false,
+            cost_per_change: 0,
+        }
+    }
+}
+
+impl Climbing {
+    /// What a step from `from` to `to` costs, if it's allowed.
+    fn step(&self, from: char, to: char) -> Option<u64> {
+        let change = altitude(to) - altitude(from);
+        let allowed = change <= self.max_ascent && self.max_descent.is_none_or(|d| -change <= d);
+        allowed.then_some(1 + u64::from(self.cost_per_change) * u64::from(change.unsigned_abs()))
+    }
 
-const INPUT: &str = include_str!("../data/Day12.txt");
+    /// The cells next to `pos`, whether or not you can step there.
+    fn adjacent(self, altitudes: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let directions: &[(isize, isize)] = if self.diagonals {
+            &ALL_DIRECTIONS
+        } else {
+            &ORTHOGONAL
+        };
+        directions
+            .iter()
+            .filter_map(move |&d| altitudes.step(pos, d))
+    }
+
+    /// A lower bound on the cost from `pos` to `end`.
+    fn estimate(&self, altitudes: &Grid<char>, pos: Pos, end: Pos) -> u64 {
+        let (a, b) = (Point2::from(pos), Point2::from(end));
+        let steps = if self.diagonals {
+            a.chebyshev(b)
+        } else {
+            a.manhattan(b)
+        };
+        let climb = (altitude(altitudes[end]) - altitude(altitudes[pos])).unsigned_abs();
+        steps as u64 + u64::from(self.cost_per_change) * u64::from(climb)
+    }
+}
+
+/// `climbing` is how you can move: the puzzle's rules by default.
+#[derive(Default)]
+pub struct Day12 {
+    pub climbing: Climbing,
+}
 
 fn altitude(ch: char) -> i32 {
     match ch {
@@ -13,144 +83,291 @@ fn altitude(ch: char) -> i32 {
     }
 }
 
-fn manhattan_distance((x1, y1): (i32, i32), (x2, y2): (i32, i32)) -> i32 {
-    (x1 - x2).abs() + (y1 - y2).abs()
+impl Day12 {
+    /// The cheapest route from any of `starts` to `E`.
+    fn climb(
+        &self,
+        altitudes: &Grid<char>,
+        starts: impl Iterator<Item = Pos>,
+    ) -> Result<Route<Pos, u64>> {
+        let climbing = self.climbing;
+        let end = altitudes.find(|&c| c == 'E').unwrap();
+        search::astar(
+            starts,
+            |&pos| {
+                climbing.adjacent(altitudes, pos).filter_map(move |p| {
+                    climbing
+                        .step(altitudes[pos], altitudes[p])
+                        .map(|cost| (p, cost))
+                })
+            },
+            |&pos| climbing.estimate(altitudes, pos, end),
+            |&pos| pos == end,
+        )
+        .ok_or_else(|| AocError::solve("`E` can't be reached"))
+    }
+
+    /// Search backwards from `E`, stepping wherever you could have climbed
+    /// from, to find the cheapest way from every cell to the summit in one
+    /// pass. The cost of a cell is its distance to `E`, and the path to it,
+    /// reversed, is its route up; cells with no way up aren't reached.
+    pub fn distances_to_summit(&self, altitudes: &Grid<char>) -> Reached<Pos, u64> {
+        let climbing = self.climbing;
+        let end = altitudes.find(|&c| c == 'E').unwrap();
+        search::explore([end], |&pos| {
+            climbing.adjacent(altitudes, pos).filter_map(move |p| {
+                climbing
+                    .step(altitudes[p], altitudes[pos])
+                    .map(|cost| (p, cost))
+            })
+        })
+    }
+
+    /// The part 1 search from `S` to `E`.
+    pub fn climb_from_start(&self, altitudes: &Grid<char>) -> Result<Route<Pos, u64>> {
+        self.climb(altitudes, altitudes.find(|&c| c == 'S').into_iter())
+    }
+
+    /// The route from `S` to `E` for part 1.
+    pub fn route_from_start(&self, altitudes: &Grid<char>) -> Result<Vec<Pos>> {
+        self.climb_from_start(altitudes).map(|r| r.path)
+    }
+
+    /// The cost of the route from the lowest cell cheapest to `E`, for part
+    /// 2, with the route.
+    pub fn best_hike(&self, altitudes: &Grid<char>) -> Result<(u64, Vec<Pos>)> {
+        let reached = self.distances_to_summit(altitudes);
+        let (start, cost) = reached
+            .costs()
+            .filter(|&(&p, _)| altitude(altitudes[p]) == 0)
+            .min_by_key(|&(&p, cost)| (cost, p))
+            .ok_or_else(|| AocError::solve("`E` can't be reached from the lowest ground"))?;
+        let mut path = reached.path_to(start).unwrap();
+        path.reverse();
+        Ok((cost, path))
+    }
 }
 
-fn part1(input: &str) -> i32 {
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    let altitudes: Vec<Vec<char>> = eol.split(input).map(|s| s.chars().collect()).collect();
-    let mut steps: Vec<Vec<i32>> = altitudes
-        .iter()
-        .map(|s| s.iter().map(|_| i32::MAX).collect())
-        .collect();
-
-    let mut start: (i32, i32) = (0, 0);
-    let mut end: (i32, i32) = (0, 0);
-
-    for y in 0..altitudes.len() {
-        let row = &altitudes[y];
-        for x in 0..row.len() {
-            if row[x] == 'S' {
-                start = (x as i32, y as i32);
-                steps[y][x] = 0;
-            }
-            if row[x] == 'E' {
-                end = (x as i32, y as i32);
-            }
-        }
+/// The heightmap with `route` drawn over it, each step an arrow pointing to
+/// the next.
+pub fn route_map(altitudes: &Grid<char>, route: &[Pos]) -> Grid<char> {
+    let mut map = altitudes.clone();
+    for (&(x1, y1), &(x2, y2)) in route.iter().zip(route.iter().skip(1)) {
+        map[(x1, y1)] = match (x2 as isize - x1 as isize, y2 as isize - y1 as isize) {
+            (1, 0) => '>',
+            (-1, 0) => '<',
+            (0, -1) => '^',
+            (0, 1) => 'v',
+            (1, -1) => '↗',
+            (1, 1) => '↘',
+            (-1, 1) => '↙',
+            _ => '↖',
+        };
     }
+    map
+}
 
-    let mut outstanding: PriorityQueue<(i32, i32), Reverse<i32>> = PriorityQueue::new();
-
-    // Load start position, with priority based on manhattan distance
-    outstanding.push(start, Reverse(manhattan_distance(start, end)));
-
-    while outstanding.len() > 0 {
-        if let Some(((x, y), _)) = outstanding.pop() {
-            let my_steps = steps[y as usize][x as usize];
-            if my_steps < steps[end.1 as usize][end.0 as usize] {
-                let my_altitude = altitude(altitudes[y as usize][x as usize]);
-                for point in vec![(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-                    if point.1 >= 0 && point.1 < (altitudes.len() as i32) {
-                        let dy: usize = point.1 as usize;
-                        let row = &altitudes[dy];
-                        if point.0 >= 0 && point.0 < row.len() as i32 {
-                            let dx: usize = point.0 as usize;
-                            // Can I move to this spot, and is it worth it?
-                            if my_altitude + 1 >= altitude(row[dx]) && steps[dy][dx] > my_steps + 1
-                            {
-                                steps[dy][dx] = my_steps + 1;
-                                let priority =
-                                    Reverse(my_steps + 1 + manhattan_distance(point, end));
-                                outstanding.push(point, priority);
-                            }
-                        }
-                    }
-                }
-            }
+pub fn render_route(altitudes: &Grid<char>, route: &[Pos]) -> String {
+    route_map(altitudes, route).to_string()
+}
+
+/// Colours for a picture of a search: the ground shaded from green valleys
+/// to snowy peaks, the cells the search visited tinted from blue to cyan in
+/// the order it got to them, and the route found in red.
+pub fn shade(altitudes: &Grid<char>, route: &Route<Pos, u64>) -> Grid<Rgb> {
+    const GROUND: [Rgb; 4] = [
+        [40, 110, 40],
+        [150, 170, 70],
+        [130, 90, 50],
+        [245, 245, 245],
+    ];
+    let mut image = altitudes.map(|&c| render::gradient(&GROUND, altitude(c) as f64 / 26.0));
+    let visited = route.visited.len().max(2) - 1;
+    for (i, &pos) in route.visited.iter().enumerate() {
+        let tint = render::blend([0, 0, 255], [0, 255, 255], i as f64 / visited as f64);
+        image[pos] = render::blend(image[pos], tint, 0.6);
+    }
+    for &pos in &route.path {
+        image[pos] = [220, 30, 30];
+    }
+    for (pos, &c) in altitudes.iter() {
+        if c == 'S' || c == 'E' {
+            image[pos] = [255, 215, 0];
         }
     }
-    steps[end.1 as usize][end.0 as usize]
+    image
 }
 
-fn part2(input: &str) -> i32 {
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    let altitudes: Vec<Vec<char>> = eol.split(input).map(|s| s.chars().collect()).collect();
-    let mut steps: Vec<Vec<i32>> = altitudes
-        .iter()
-        .map(|s| s.iter().map(|_| i32::MAX).collect())
-        .collect();
-
-    let mut starts: Vec<(i32, i32)> = Vec::new();
-    let mut end: (i32, i32) = (0, 0);
-
-    for y in 0..altitudes.len() {
-        let row = &altitudes[y];
-        for x in 0..row.len() {
-            if row[x] == 'S' || row[x] == 'a' {
-                starts.push((x as i32, y as i32));
-                steps[y][x] = 0;
-            }
-            if row[x] == 'E' {
-                end = (x as i32, y as i32);
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Grid<char>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Grid<char>> {
+        let altitudes = Grid::parse(input, "of `a`-`z`, `S` and `E`", |c| {
+            (c.is_ascii_lowercase() || c == 'S' || c == 'E').then_some(c)
+        })?;
+        for mark in ['S', 'E'] {
+            let count = altitudes.iter().filter(|&(_, &c)| c == mark).count();
+            if count != 1 {
+                return Err(AocError::solve(format!(
+                    "the heightmap has {} `{}`s rather than one",
+                    count, mark
+                )));
             }
         }
+        Ok(altitudes)
     }
 
-    let mut outstanding: PriorityQueue<(i32, i32), Reverse<i32>> = PriorityQueue::new();
-
-    for start in starts {
-        // Load start position, with priority based on manhattan distance
-        outstanding.push(start, Reverse(manhattan_distance(start, end)));
-    }
-
-    while outstanding.len() > 0 {
-        if let Some(((x, y), _)) = outstanding.pop() {
-            let my_steps = steps[y as usize][x as usize];
-            if my_steps < steps[end.1 as usize][end.0 as usize] {
-                let my_altitude = altitude(altitudes[y as usize][x as usize]);
-                for point in vec![(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-                    if point.1 >= 0 && point.1 < (altitudes.len() as i32) {
-                        let dy: usize = point.1 as usize;
-                        let row = &altitudes[dy];
-                        if point.0 >= 0 && point.0 < row.len() as i32 {
-                            let dx: usize = point.0 as usize;
-                            // Can I move to this spot, and is it worth it?
-                            if my_altitude + 1 >= altitude(row[dx]) && steps[dy][dx] > my_steps + 1
-                            {
-                                steps[dy][dx] = my_steps + 1;
-                                let priority =
-                                    Reverse(my_steps + 1 + manhattan_distance(point, end));
-                                outstanding.push(point, priority);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    fn part1(&self, altitudes: &Grid<char>) -> Result<u64> {
+        self.climb_from_start(altitudes).map(|r| r.cost)
     }
-    steps[end.1 as usize][end.0 as usize]
-}
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+    fn part2(&self, altitudes: &Grid<char>) -> Result<u64> {
+        self.best_hike(altitudes).map(|(cost, _)| cost)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day12_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 31);
+        assert_eq!(
+            Day12::default()
+                .part1(&Day12::default().parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            31
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 29);
+        assert_eq!(
+            Day12::default()
+                .part2(&Day12::default().parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            29
+        );
+    }
+
+    #[test]
+    fn routes_work() {
+        let day = Day12::default();
+        let altitudes = day.parse(&normalise(TEST_INPUT)).unwrap();
+        let arrows = |map: &str| map.chars().filter(|c| "<>^v".contains(*c)).count();
+
+        let route = day.route_from_start(&altitudes).unwrap();
+        assert_eq!(route.first(), Some(&(0, 0)));
+        assert_eq!(route.last(), Some(&(5, 2)));
+        let map = render_route(&altitudes, &route);
+        assert_eq!(arrows(&map), 31);
+        assert_eq!(map.lines().nth(2).unwrap().chars().nth(5), Some('E'));
+        assert!(!map.contains('S'));
+
+        let (cost, hike) = day.best_hike(&altitudes).unwrap();
+        assert_eq!((cost, hike.len()), (29, 30));
+        assert_eq!(altitudes[hike[0]], 'a');
+        assert_eq!(arrows(&render_route(&altitudes, &hike)), 29);
+
+        let search = day.climb_from_start(&altitudes).unwrap();
+        let image = shade(&altitudes, &search);
+        assert_eq!(image[(1, 0)], [220, 30, 30]);
+        assert_eq!(image[(0, 0)], [255, 215, 0]);
+    }
+
+    #[test]
+    fn distances_to_summit_work() {
+        let day = Day12::default();
+        let altitudes = day.parse(&normalise(TEST_INPUT)).unwrap();
+        let distances = day.distances_to_summit(&altitudes);
+        assert_eq!(distances.cost(&(0, 0)), Some(31));
+        assert_eq!(distances.cost(&(5, 2)), Some(0));
+        assert_eq!(distances.cost(&(0, 4)), Some(29));
+        assert_eq!(distances.costs().count(), altitudes.iter().count());
+        let path = distances.path_to(&(0, 0)).unwrap();
+        assert_eq!((path.len(), path[0]), (32, (5, 2)));
+
+        // Nothing climbs the cliff onto the `z`, so nothing below it is reached
+        let cliff = day.parse("Sz\naE").unwrap();
+        let distances = day.distances_to_summit(&cliff);
+        assert_eq!(distances.cost(&(1, 0)), Some(1));
+        assert_eq!(distances.cost(&(0, 1)), None);
+    }
+
+    #[test]
+    fn climbing_policies_work() {
+        let altitudes = Day12::default().parse(&normalise(TEST_INPUT)).unwrap();
+        let answers = |climbing: Climbing| {
+            let day = Day12 { climbing };
+            (day.part1(&altitudes).ok(), day.part2(&altitudes).ok())
+        };
+        let puzzle = Climbing::default();
+        assert_eq!(answers(puzzle), (Some(31), Some(29)));
+        assert_eq!(
+            answers(Climbing {
+                max_ascent: 2,
+                ..puzzle
+            }),
+            (Some(31), Some(27))
+        );
+        assert_eq!(
+            answers(Climbing {
+                max_ascent: 0,
+                ..puzzle
+            }),
+            (None, None)
+        );
+        assert_eq!(
+            answers(Climbing {
+                diagonals: true,
+                ..puzzle
+            }),
+            (Some(28), Some(27))
+        );
+        assert_eq!(
+            answers(Climbing {
+                cost_per_change: 1,
+                ..puzzle
+            }),
+            (Some(57), Some(55))
+        );
+        let dearest = u64::from(u32::MAX);
+        assert_eq!(
+            answers(Climbing {
+                cost_per_change: u32::MAX,
+                ..puzzle
+            }),
+            (Some(31 + 26 * dearest), Some(29 + 26 * dearest))
+        );
+
+        // Nowhere in the example needs a drop, so test that on a ridge
+        let ridge = Day12::default().parse("SzaE").unwrap();
+        let steep = Climbing {
+            max_ascent: 26,
+            ..puzzle
+        };
+        let climb = |climbing: Climbing| Day12 { climbing }.part1(&ridge).ok();
+        assert_eq!(climb(steep), Some(3));
+        assert_eq!(
+            climb(Climbing {
+                max_descent: Some(24),
+                ..steep
+            }),
+            None
+        );
+        assert_eq!(
+            climb(Climbing {
+                max_descent: Some(25),
+                ..steep
+            }),
+            Some(3)
+        );
     }
 }
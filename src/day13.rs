@@ -10,7 +10,9 @@ use nom::{
     IResult,
 };
 
-const INPUT: &str = include_str!("../data/Day13.txt");
+use crate::{error::Result, input::parse_all, Solution};
+
+pub struct Day13;
 
 fn integerp(input: &str) -> IResult<&str, i32> {
     map_res(recognize(many1(one_of("0123456789"))), |s: &str| {
@@ -19,7 +21,7 @@ fn integerp(input: &str) -> IResult<&str, i32> {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-enum Item {
+pub enum Item {
     Num(i32),
     List(Vec<Item>),
 }
@@ -44,12 +46,7 @@ impl Display for Item {
 
 impl PartialOrd for Item {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (self, other) {
-            (Num(a), Num(b)) => Some(a.cmp(b)),
-            (List(a), List(b)) => a.partial_cmp(b),
-            (Num(a), List(_)) => List(vec![Num(*a)]).partial_cmp(other),
-            (List(_), Num(b)) => self.partial_cmp(&List(vec![Num(*b)])),
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -66,10 +63,10 @@ impl Ord for Item {
 
 fn item(input: &str) -> IResult<&str, Item> {
     alt((
-        map(integerp, |i| Num(i)),
+        map(integerp, Num),
         map(
             delimited(tag("["), separated_list0(tag(","), item), tag("]")),
-            |l| List(l),
+            List,
         ),
     ))(input)
 }
@@ -81,48 +78,54 @@ fn itempair(input: &str) -> IResult<&str, (Item, Item)> {
     )(input)
 }
 
-fn part1(input: &str) -> usize {
-    let all_items = separated_list1(line_ending, itempair)(input).unwrap().1;
-    let items = all_items
-        .iter()
-        .enumerate()
-        .filter(|(_, (a, b))| a.cmp(b) != Ordering::Greater)
-        .collect::<Vec<_>>();
-    items.iter().map(|(s, _)| s + 1).sum()
-}
+impl Solution for Day13 {
+    const DAY: u32 = 13;
 
-fn part2(input: &str) -> usize {
-    let all_pairs = separated_list1(line_ending, itempair)(input).unwrap().1;
-    let mut all_items = all_pairs
-        .iter()
-        .flat_map(|(a, b)| vec![a, b])
-        .collect::<Vec<_>>();
-    let div1 = List(vec![List(vec![Num(2)])]);
-    let div2 = List(vec![List(vec![Num(6)])]);
-    all_items.push(&div1);
-    all_items.push(&div2);
-    all_items.sort();
-
-    for (i, item) in all_items.iter().enumerate() {
-        println!("{}: {}", i, item);
+    type Input = Vec<(Item, Item)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<(Item, Item)>> {
+        parse_all(
+            input,
+            "a pair of packets",
+            separated_list1(line_ending, itempair)(input),
+        )
     }
 
-    all_items
-        .into_iter()
-        .enumerate()
-        .filter(|(_, s)| **s == div1 || **s == div2)
-        .map(|(s, _)| s + 1)
-        .product()
-}
+    fn part1(&self, all_items: &Vec<(Item, Item)>) -> Result<usize> {
+        let items = all_items
+            .iter()
+            .enumerate()
+            .filter(|(_, (a, b))| a.cmp(b) != Ordering::Greater)
+            .collect::<Vec<_>>();
+        Ok(items.iter().map(|(s, _)| s + 1).sum())
+    }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+    fn part2(&self, all_pairs: &Vec<(Item, Item)>) -> Result<usize> {
+        let mut all_items = all_pairs
+            .iter()
+            .flat_map(|(a, b)| vec![a, b])
+            .collect::<Vec<_>>();
+        let div1 = List(vec![List(vec![Num(2)])]);
+        let div2 = List(vec![List(vec![Num(6)])]);
+        all_items.push(&div1);
+        all_items.push(&div2);
+        all_items.sort();
+
+        Ok(all_items
+            .into_iter()
+            .enumerate()
+            .filter(|(_, s)| **s == div1 || **s == div2)
+            .map(|(s, _)| s + 1)
+            .product())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day13_test.txt");
 
@@ -140,11 +143,21 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 13);
+        assert_eq!(
+            Day13
+                .part1(&Day13.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            13
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 140);
+        assert_eq!(
+            Day13
+                .part2(&Day13.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            140
+        );
     }
 }
@@ -9,15 +9,21 @@ use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, one_of},
     combinator::{map, map_res, recognize},
-    multi::{many1, separated_list0, separated_list1},
+    multi::{many1, separated_list1},
     sequence::separated_pair,
     IResult,
 };
 
-const INPUT: &str = include_str!("../data/Day14.txt");
+use crate::{
+    error::{AocError, Result},
+    geometry::{Bounds, Compass, Point2},
+    input::parse_all,
+    Solution,
+};
+
+pub struct Day14;
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Point(i32, i32);
+pub type Point = Point2<i32>;
 
 #[derive(Clone, Copy)]
 enum Line {
@@ -30,8 +36,8 @@ use Line::*;
 impl Line {
     fn points(&self) -> Vec<Point> {
         match self {
-            HLine(x, y1, y2) => (*y1..(*y2) + 1).map(|y| Point(*x, y)).collect(),
-            VLine(x1, x2, y) => (*x1..(*x2) + 1).map(|x| Point(x, *y)).collect(),
+            HLine(x, y1, y2) => (*y1..(*y2) + 1).map(|y| Point::new(*x, y)).collect(),
+            VLine(x1, x2, y) => (*x1..(*x2) + 1).map(|x| Point::new(x, *y)).collect(),
         }
     }
 }
@@ -43,20 +49,18 @@ fn integerp(input: &str) -> IResult<&str, i32> {
 }
 
 fn point(input: &str) -> IResult<&str, Point> {
-    map(separated_pair(integerp, tag(","), integerp), |(a, b)| {
-        Point(a, b)
-    })(input)
+    map(separated_pair(integerp, tag(","), integerp), Point::from)(input)
 }
 
-fn points_to_lines(points: &Vec<Point>) -> Vec<Line> {
+fn points_to_lines(points: &[Point]) -> Vec<Line> {
     points
         .iter()
         .tuple_windows::<(_, _)>()
-        .map(|(Point(x1, y1), Point(x2, y2))| {
-            if *x1 == *x2 {
-                HLine(*x1, min(*y1, *y2), max(*y1, *y2))
+        .map(|(a, b)| {
+            if a.x == b.x {
+                HLine(a.x, min(a.y, b.y), max(a.y, b.y))
             } else {
-                VLine(min(*x1, *x2), max(*x1, *x2), *y1)
+                VLine(min(a.x, b.x), max(a.x, b.x), a.y)
             }
         })
         .collect()
@@ -66,108 +70,123 @@ fn lines(input: &str) -> IResult<&str, Vec<Line>> {
     map(separated_list1(tag(" -> "), point), |s| points_to_lines(&s))(input)
 }
 
-fn part1(input: &str) -> usize {
-    let lines = separated_list0(line_ending, lines)(input).unwrap().1;
+/// Where a grain of sand tries to go, in order of preference.
+const FALLS: [Compass; 3] = [Compass::S, Compass::SW, Compass::SE];
 
-    let mut populated_points: HashSet<Point> = lines
-        .iter()
-        .flat_map(|s| s.iter().flat_map(|p| p.points().into_iter()))
-        .collect();
-
-    let abyss_y = populated_points.iter().map(|Point(_, y)| y).max().unwrap() + 1;
-
-    let start = Point(500, 0);
-
-    let mut sand: usize = 0;
-
-    let mut done = false;
-
-    while !done {
-        let mut sand_x = start.0;
-        let mut sand_y = start.1;
-        let mut stopped = false;
-        while !stopped && sand_y < abyss_y {
-            let state = (
-                populated_points.contains(&Point(sand_x - 1, sand_y + 1)),
-                populated_points.contains(&Point(sand_x, sand_y + 1)),
-                populated_points.contains(&Point(sand_x + 1, sand_y + 1)),
-            );
-            (sand_x, sand_y, stopped) = match state {
-                (_, false, _) => (sand_x, sand_y + 1, false),
-                (false, true, _) => (sand_x - 1, sand_y + 1, false),
-                (true, true, false) => (sand_x + 1, sand_y + 1, false),
-                (true, true, true) => (sand_x, sand_y, true),
+fn lowest_rock(rock: &HashSet<Point>) -> Result<i32> {
+    Bounds::of(rock.iter().copied())
+        .map(|b| b.max.y)
+        .ok_or_else(|| AocError::solve("there's no rock in the cave"))
+}
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    type Input = HashSet<Point>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<HashSet<Point>> {
+        let lines = parse_all(
+            input,
+            "a path of `x,y` points joined by ` -> `",
+            separated_list1(line_ending, lines)(input),
+        )?;
+        Ok(lines
+            .iter()
+            .flat_map(|s| s.iter().flat_map(|p| p.points().into_iter()))
+            .collect())
+    }
+
+    fn part1(&self, rock: &HashSet<Point>) -> Result<usize> {
+        let mut populated_points = rock.clone();
+
+        let abyss_y = lowest_rock(rock)? + 1;
+
+        let start = Point::new(500, 0);
+
+        let mut grains: usize = 0;
+
+        let mut done = false;
+
+        while !done {
+            let mut sand = start;
+            while sand.y < abyss_y {
+                match FALLS
+                    .iter()
+                    .map(|d| sand + d.delta())
+                    .find(|p| !populated_points.contains(p))
+                {
+                    Some(p) => sand = p,
+                    None => break,
+                }
+            }
+            done = sand.y == abyss_y;
+            if !done {
+                grains += 1;
+                populated_points.insert(sand);
             }
         }
-        done = sand_y == abyss_y;
-        if !done {
-            sand += 1;
-            populated_points.insert(Point(sand_x, sand_y));
-        }
+        Ok(grains)
     }
-    sand
-}
 
-fn part2(input: &str) -> usize {
-    let lines = separated_list0(line_ending, lines)(input).unwrap().1;
+    fn part2(&self, rock: &HashSet<Point>) -> Result<usize> {
+        let mut populated_points = rock.clone();
 
-    let mut populated_points: HashSet<Point> = lines
-        .iter()
-        .flat_map(|s| s.iter().flat_map(|p| p.points().into_iter()))
-        .collect();
-
-    let lowest_y = populated_points.iter().map(|Point(_, y)| y).max().unwrap() + 1;
-
-    let start = Point(500, 0);
-
-    let mut sand: usize = 0;
-
-    let mut done = false;
-
-    while !done {
-        let mut sand_x = start.0;
-        let mut sand_y = start.1;
-        let mut stopped = false;
-        while !stopped && sand_y < lowest_y {
-            let state = (
-                populated_points.contains(&Point(sand_x - 1, sand_y + 1)),
-                populated_points.contains(&Point(sand_x, sand_y + 1)),
-                populated_points.contains(&Point(sand_x + 1, sand_y + 1)),
-            );
-            (sand_x, sand_y, stopped) = match state {
-                (_, false, _) => (sand_x, sand_y + 1, false),
-                (false, true, _) => (sand_x - 1, sand_y + 1, false),
-                (true, true, false) => (sand_x + 1, sand_y + 1, false),
-                (true, true, true) => (sand_x, sand_y, true),
+        let lowest_y = lowest_rock(rock)? + 1;
+
+        let start = Point::new(500, 0);
+
+        let mut grains: usize = 0;
+
+        let mut done = false;
+
+        while !done {
+            let mut sand = start;
+            while sand.y < lowest_y {
+                match FALLS
+                    .iter()
+                    .map(|d| sand + d.delta())
+                    .find(|p| !populated_points.contains(p))
+                {
+                    Some(p) => sand = p,
+                    None => break,
+                }
+            }
+            done = populated_points.contains(&start);
+            if !done {
+                grains += 1;
+                populated_points.insert(sand);
             }
         }
-        done = populated_points.contains(&start);
-        if !done {
-            sand += 1;
-            populated_points.insert(Point(sand_x, sand_y));
-        }
+        Ok(grains)
     }
-    sand
-}
-
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day14_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 24);
+        assert_eq!(
+            Day14
+                .part1(&Day14.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            24
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 93);
+        assert_eq!(
+            Day14
+                .part2(&Day14.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            93
+        );
     }
 }
@@ -4,53 +4,65 @@ use std::{
     str::FromStr,
 };
 
-const INPUT: &str = include_str!("../data/Day15.txt");
+use crate::{
+    error::{AocError, Result},
+    geometry::Point2,
+    input::parse_lines,
+    Solution,
+};
+
+/// `line` is the row part 1 counts covered positions on, and `max` bounds the
+/// search square for the distress beacon in part 2.
+pub struct Day15 {
+    pub line: i32,
+    pub max: i32,
+}
 
-#[derive(Debug, PartialEq, Eq, Hash,Clone,Copy)]
-struct Point(i32, i32);
+impl Default for Day15 {
+    fn default() -> Self {
+        Day15 {
+            line: 2_000_000,
+            max: 4_000_000,
+        }
+    }
+}
+
+type Point = Point2<i32>;
 
 #[derive(Debug, Clone, Copy)]
-struct SensorInfo {
+pub struct SensorInfo {
     loc: Point,
     closest_beacon: Point,
 }
 
-#[derive(Debug)]
-struct ParseError {}
-
 impl FromStr for SensorInfo {
-    type Err = ParseError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let re = Regex::new(
-            "Sensor at x=(-?[0-9]+), y=(-?[0-9]+): closest beacon is at x=(-?[0-9]+), y=(-?[0-9]+)",
+            "^Sensor at x=(-?[0-9]+), y=(-?[0-9]+): closest beacon is at x=(-?[0-9]+), y=(-?[0-9]+)$",
         )
         .unwrap();
-        let cap = re.captures(s).unwrap();
+        let error = || {
+            AocError::parse(
+                s,
+                "`Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>`",
+            )
+        };
+        let cap = re.captures(s).ok_or_else(error)?;
+        let n = |i: usize| cap[i].parse::<i32>().map_err(|_| error());
         Ok(SensorInfo {
-            loc: Point(
-                cap[1].parse::<i32>().unwrap(),
-                cap[2].parse::<i32>().unwrap(),
-            ),
-            closest_beacon: Point(
-                cap[3].parse::<i32>().unwrap(),
-                cap[4].parse::<i32>().unwrap(),
-            ),
+            loc: Point::new(n(1)?, n(2)?),
+            closest_beacon: Point::new(n(3)?, n(4)?),
         })
     }
 }
 
-impl Point {
-    fn manhattan_distance(&self, p: &Point) -> i32 {
-        (self.0 - p.0).abs() + (self.1 - p.1).abs()
-    }
-}
-
 impl SensorInfo {
     fn covered_at(&self, line: i32) -> Option<(i32, i32)> {
-        let covered_manhattan = self.loc.manhattan_distance(&self.closest_beacon);
-        let sensor_x = self.loc.0;
-        let sensor_y = self.loc.1;
+        let covered_manhattan = self.loc.manhattan(self.closest_beacon);
+        let sensor_x = self.loc.x;
+        let sensor_y = self.loc.y;
         let sensor_perpendicular_distance = (line - sensor_y).abs();
 
         if sensor_perpendicular_distance <= covered_manhattan {
@@ -62,103 +74,103 @@ impl SensorInfo {
     }
 
     fn just_not_covered(&self) -> HashSet<Point> {
-        let covered_manhattan =
-            (self.loc.0 - self.closest_beacon.0).abs() + (self.loc.1 - self.closest_beacon.1).abs()+1;
+        let covered_manhattan = self.loc.manhattan(self.closest_beacon) + 1;
 
-        let sensor_x = self.loc.0;
-        let sensor_y = self.loc.1;
+        let sensor_x = self.loc.x;
+        let sensor_y = self.loc.y;
 
-        (0..covered_manhattan+1)
+        (0..covered_manhattan + 1)
             .map(|d| (d, covered_manhattan - d))
             .flat_map(|(dx, dy)| {
                 vec![
-                    Point(sensor_x + dx, sensor_x + dy),
-                    Point(sensor_x + dx, sensor_y - dy),
-                    Point(sensor_x - dx, sensor_y - dy),
-                    Point(sensor_x - dx, sensor_y + dy),
+                    Point::new(sensor_x + dx, sensor_x + dy),
+                    Point::new(sensor_x + dx, sensor_y - dy),
+                    Point::new(sensor_x - dx, sensor_y - dy),
+                    Point::new(sensor_x - dx, sensor_y + dy),
                 ]
                 .into_iter()
             })
             .collect()
     }
 
-    fn hides(&self, p : &Point) -> bool {
-        return self.loc.manhattan_distance(p) <= self.loc.manhattan_distance(&self.closest_beacon)
+    fn hides(&self, p: &Point) -> bool {
+        self.loc.manhattan(*p) <= self.loc.manhattan(self.closest_beacon)
     }
 }
 
-fn part1(input: &str, line: i32) -> usize {
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    let sensors = eol
-        .split(input)
-        .filter(|s| s.len() > 0)
-        .map(|s| s.parse::<SensorInfo>().unwrap())
-        .collect::<Vec<_>>();
-    let beacons = sensors
-        .iter()
-        .filter(|s| s.closest_beacon.1 == line)
-        .map(|s| s.closest_beacon.0)
-        .collect::<HashSet<_>>();
-    let covered = sensors
-        .iter()
-        .filter_map(|s| s.covered_at(line))
-        .flat_map(|(x, y)| x..y)
-        .collect::<HashSet<_>>();
-    covered.len() - beacons.len()
-}
+impl Solution for Day15 {
+    const DAY: u32 = 15;
 
-fn part2(input: &str, max: i32) -> usize {
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    let sensors = eol
-        .split(input)
-        .filter(|s| s.len() > 0)
-        .map(|s| s.parse::<SensorInfo>().unwrap())
-        .collect::<Vec<_>>();
-
-    let possibly_not_covered = sensors
-        .iter()
-        .flat_map(|s| s.just_not_covered().into_iter())
-        .filter(|p| p.0 >= 0 && p.0 <= max && p.1 >= 0 && p.1 <= max)
-        .fold(HashMap::new(), |mut m, s| {
-            *(m.entry(s).or_insert(0)) += 1;
-            m
-        });
-
-    // If there's only one location, *at least* two areas have to border on this one - might be more,
-    // but we can automatically exclude any point that is only in one boundary
-    let need_checking = possibly_not_covered
-        .iter()
-        .filter_map(|(k, v)| match v {
-            1 => None,
-            _ => Some(k),
-        })
-        .copied()
-        .collect::<Vec<_>>();
+    type Input = Vec<SensorInfo>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    println!("Needs Checking: {:?}", need_checking);
+    fn parse(&self, input: &str) -> Result<Vec<SensorInfo>> {
+        parse_lines(input, str::parse)
+    }
 
-    // iterator through things that need checking, seeing if they are hidden by any of the sensors
-    let result = need_checking.into_iter().filter(|p| sensors.iter().all(|s| !s.hides(p))).collect::<Vec<_>>();
-    println!("Result: {:?}", result);
-    (result[0].0 as usize) * 4_000_000 + (result[0].1 as usize)
-}
+    fn part1(&self, sensors: &Vec<SensorInfo>) -> Result<usize> {
+        let line = self.line;
+        let beacons = sensors
+            .iter()
+            .filter(|s| s.closest_beacon.y == line)
+            .map(|s| s.closest_beacon.x)
+            .collect::<HashSet<_>>();
+        let covered = sensors
+            .iter()
+            .filter_map(|s| s.covered_at(line))
+            .flat_map(|(x, y)| x..y)
+            .collect::<HashSet<_>>();
+        Ok(covered.len() - beacons.len())
+    }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT, 2_000_000));
-    println!("Part 2: {}", part2(INPUT, 4_000_000));
+    fn part2(&self, sensors: &Vec<SensorInfo>) -> Result<usize> {
+        let max = self.max;
+        let possibly_not_covered = sensors
+            .iter()
+            .flat_map(|s| s.just_not_covered().into_iter())
+            .filter(|p| p.x >= 0 && p.x <= max && p.y >= 0 && p.y <= max)
+            .fold(HashMap::new(), |mut m, s| {
+                *(m.entry(s).or_insert(0)) += 1;
+                m
+            });
+
+        // If there's only one location, *at least* two areas have to border on this one - might be more,
+        // but we can automatically exclude any point that is only in one boundary
+        let need_checking = possibly_not_covered
+            .iter()
+            .filter_map(|(k, v)| match v {
+                1 => None,
+                _ => Some(k),
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        // iterator through things that need checking, seeing if they are hidden by any of the sensors
+        let beacon = need_checking
+            .into_iter()
+            .find(|p| sensors.iter().all(|s| !s.hides(p)))
+            .ok_or_else(|| AocError::solve("every position is covered by a sensor"))?;
+        Ok((beacon.x as usize) * 4_000_000 + (beacon.y as usize))
+    }
+
+    fn for_examples(&self) -> Option<Day15> {
+        Some(Day15 { line: 10, max: 20 })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day15_test.txt");
 
     #[test]
     fn covered_at_works() {
         let s = SensorInfo {
-            loc: Point(8, 7),
-            closest_beacon: Point(2, 10),
+            loc: Point::new(8, 7),
+            closest_beacon: Point::new(2, 10),
         };
         assert_eq!(s.covered_at(20), None);
         assert_eq!(s.covered_at(17), None);
@@ -169,28 +181,38 @@ mod tests {
     #[test]
     fn just_not_covered_works() {
         let s = SensorInfo {
-            loc: Point(0, 0),
-            closest_beacon: Point(0, 1),
+            loc: Point::new(0, 0),
+            closest_beacon: Point::new(0, 1),
         };
         let mut expected: HashSet<Point> = HashSet::new();
-        expected.insert(Point(2,0));
-        expected.insert(Point(-2,0));
-        expected.insert(Point(0,2));
-        expected.insert(Point(0,-2));
-        expected.insert(Point(1,1));
-        expected.insert(Point(-1,1));
-        expected.insert(Point(1,-1));
-        expected.insert(Point(-1,-1));
+        expected.insert(Point::new(2, 0));
+        expected.insert(Point::new(-2, 0));
+        expected.insert(Point::new(0, 2));
+        expected.insert(Point::new(0, -2));
+        expected.insert(Point::new(1, 1));
+        expected.insert(Point::new(-1, 1));
+        expected.insert(Point::new(1, -1));
+        expected.insert(Point::new(-1, -1));
         assert_eq!(s.just_not_covered(), expected)
     }
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT, 10), 26);
+        let day = Day15 { line: 10, max: 20 };
+        assert_eq!(
+            day.part1(&day.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            26
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT, 20), 56000011);
+        let day = Day15 { line: 10, max: 20 };
+        assert_eq!(
+            day.part2(&day.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            56000011
+        );
     }
 }
@@ -1,66 +1,683 @@
-use std::str::FromStr;
+use std::{collections::HashMap, fmt::Write};
 
-use regex::Regex;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, line_ending, u32},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+    IResult,
+};
 
-const INPUT: &str = include_str!("../data/Day16.txt");
+use crate::{
+    error::{AocError, Result},
+    input::parse_all,
+    search, Solution,
+};
 
-#[derive(Debug)]
-struct ValveInfo {
-    name: String,
-    flow: i32,
-    tunnels_to: Vec<String>,
+/// Part 1 has `minutes` alone; part 2 has `agents` working together, each
+/// with `team_minutes`.
+pub struct Day16 {
+    pub minutes: u32,
+    pub agents: usize,
+    pub team_minutes: u32,
 }
 
-#[derive(Debug)]
-struct ParseError {}
+impl Default for Day16 {
+    fn default() -> Self {
+        Day16 {
+            minutes: 30,
+            agents: 2,
+            team_minutes: 26,
+        }
+    }
+}
 
-impl FromStr for ValveInfo {
-    type Err = ParseError;
+/// A line of the input, naming a valve, its flow rate and where its
+/// tunnels lead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValveInfo {
+    pub name: String,
+    pub flow: u32,
+    pub tunnels_to: Vec<String>,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new(
-            "Valve ([A-Z]{2}) has flow rate=([0-9]+); tunnels lead to valves ([A-Z]{2}(?:, [A-Z]{2})*)",
-        )
-        .unwrap();
-        let cap = re.captures(s).unwrap();
-        Ok(ValveInfo {
-            name: cap[1].to_string(),
-            flow: cap[2].parse::<i32>().unwrap(),
-            tunnels_to: cap[3].split(", ").map(|s| s.to_string()).collect(),
+fn name(input: &str) -> IResult<&str, String> {
+    map(alphanumeric1, str::to_string)(input)
+}
+
+/// `Valve AA has flow rate=0; tunnels lead to valves DD, II, BB`, or for a
+/// single tunnel `tunnel leads to valve GG`.
+fn valve(input: &str) -> IResult<&str, ValveInfo> {
+    map(
+        tuple((
+            preceded(tag("Valve "), name),
+            preceded(tag(" has flow rate="), u32),
+            preceded(
+                alt((
+                    tag("; tunnels lead to valves "),
+                    tag("; tunnel leads to valve "),
+                )),
+                separated_list1(tag(", "), name),
+            ),
+        )),
+        |(name, flow, tunnels_to)| ValveInfo {
+            name,
+            flow,
+            tunnels_to,
+        },
+    )(input)
+}
+
+/// The valves and tunnels, each valve known by an index into them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValveGraph {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    flows: Vec<u32>,
+    tunnels: Vec<Vec<usize>>,
+}
+
+impl ValveGraph {
+    /// Give each valve an index, in the order listed, and follow its
+    /// tunnels to them. Every valve must be listed once.
+    pub fn new(valves: Vec<ValveInfo>) -> Result<ValveGraph> {
+        let mut index = HashMap::new();
+        for (i, valve) in valves.iter().enumerate() {
+            if index.insert(valve.name.clone(), i).is_some() {
+                return Err(AocError::solve(format!(
+                    "valve `{}` is listed twice",
+                    valve.name
+                )));
+            }
+        }
+        let tunnels = valves
+            .iter()
+            .map(|v| {
+                v.tunnels_to
+                    .iter()
+                    .map(|to| {
+                        index.get(to).copied().ok_or_else(|| {
+                            AocError::solve(format!("`{}` leads to no valve `{}`", v.name, to))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(ValveGraph {
+            flows: valves.iter().map(|v| v.flow).collect(),
+            names: valves.into_iter().map(|v| v.name).collect(),
+            index,
+            tunnels,
         })
     }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The index of the valve called `name`.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    pub fn name(&self, valve: usize) -> &str {
+        &self.names[valve]
+    }
+
+    pub fn flow(&self, valve: usize) -> u32 {
+        self.flows[valve]
+    }
+
+    /// The valves `valve`'s tunnels lead to.
+    pub fn tunnels(&self, valve: usize) -> &[usize] {
+        &self.tunnels[valve]
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let eol = Regex::new("\r\n|\r|\n").unwrap();
-    eol.split(input)
-        .filter(|s| s.len() > 0)
-        .map(|s| s.parse::<ValveInfo>().unwrap())
-        .count()
+/// The valve everyone starts at.
+pub const START: &str = "AA";
+
+/// The tunnels boiled down to what matters: the valves worth opening, and
+/// how many minutes it takes to walk between each pair of them, or to each
+/// from the start.
+#[derive(Debug, Clone)]
+pub struct Network {
+    names: Vec<String>,
+    flows: Vec<u32>,
+    /// `distances[i][j]` between useful valves `i` and `j`, with the start
+    /// as the extra last row.
+    distances: Vec<Vec<u32>>,
+}
+
+/// The best still to come from each valve, minutes left and set of valves
+/// open.
+type Memo = HashMap<(usize, u32, u64), u32>;
+
+/// A valve opened, and the minute it was opened in. It releases pressure
+/// from the next minute on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    pub valve: String,
+    pub minute: u32,
+}
+
+/// The pressure a plan releases, with the valves it opens in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    pub released: u32,
+    pub openings: Vec<Opening>,
+}
+
+/// The pressure a team releases between them, with each member's schedule.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TeamPlan {
+    pub released: u32,
+    pub schedules: Vec<Schedule>,
+}
+
+/// The most valves worth opening that a table with an entry for every
+/// subset of them can cover. At 16 the table has 65536 entries, and adding
+/// each member to a team takes 3^16, about 43 million, steps; every valve
+/// more triples that.
+pub const MAX_SUBSET_VALVES: usize = 16;
+
+impl Network {
+    /// Find the distances from `start` and each valve with a non-zero flow
+    /// to the others, searching the tunnels from each in turn.
+    pub fn new(valves: &ValveGraph, start: &str) -> Result<Network> {
+        let start = valves
+            .index(start)
+            .ok_or_else(|| AocError::solve(format!("there's no valve `{}` to start at", start)))?;
+
+        let useful = (0..valves.len())
+            .filter(|&i| valves.flow(i) > 0)
+            .collect::<Vec<_>>();
+        if useful.len() > 64 {
+            return Err(AocError::solve(format!(
+                "{} valves are worth opening, and at most 64 can be tracked",
+                useful.len()
+            )));
+        }
+        let distances = useful
+            .iter()
+            .chain([&start])
+            .map(|&from| {
+                let reached =
+                    search::explore([from], |&v| valves.tunnels(v).iter().map(|&to| (to, 1)));
+                useful
+                    .iter()
+                    .map(|to| reached.cost(to).unwrap_or(u32::MAX))
+                    .collect()
+            })
+            .collect();
+        Ok(Network {
+            names: useful.iter().map(|&i| valves.name(i).to_string()).collect(),
+            flows: useful.iter().map(|&i| valves.flow(i)).collect(),
+            distances,
+        })
+    }
+
+    /// The valves worth opening, by index.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Every order of opening valves in `allowed`, a bitmask of indices, that
+    /// fits in `minutes`: `visit` sees the valves opened so far, as a mask,
+    /// and the pressure they'll release between them.
+    pub fn search(&self, minutes: u32, allowed: u64, mut visit: impl FnMut(u64, u32)) {
+        self.extend(allowed, self.names.len(), minutes, 0, 0, &mut visit);
+    }
+
+    /// Carry on from valve `at` (the start being one past the last) with
+    /// `left` minutes to go.
+    fn extend(
+        &self,
+        allowed: u64,
+        at: usize,
+        left: u32,
+        open: u64,
+        released: u32,
+        visit: &mut impl FnMut(u64, u32),
+    ) {
+        visit(open, released);
+        for next in 0..self.names.len() {
+            if allowed & 1 << next == 0 {
+                continue;
+            }
+            if let Some((left, gain)) = self.open_next(at, left, open, next) {
+                let (open, released) = (open | 1 << next, released + gain);
+                self.extend(allowed, next, left, open, released, visit);
+            }
+        }
+    }
+
+    /// The most pressure still to be released from valve `at` (the start
+    /// being one past the last) with `left` minutes to go, once the valves
+    /// in `open` are open. The same state is reached by many orders of
+    /// opening, so each is worked out only once.
+    fn best_from(&self, at: usize, left: u32, open: u64, memo: &mut Memo) -> u32 {
+        if let Some(&best) = memo.get(&(at, left, open)) {
+            return best;
+        }
+        let mut best = 0;
+        for next in 0..self.names.len() {
+            if let Some((left, gain)) = self.open_next(at, left, open, next) {
+                best = best.max(gain + self.best_from(next, left, open | 1 << next, memo));
+            }
+        }
+        memo.insert((at, left, open), best);
+        best
+    }
+
+    /// Walking from `at` with `left` minutes to go and opening `next`, if
+    /// it's still closed and there's time for it to release anything: the
+    /// minutes left afterwards and what it'll release in them.
+    fn open_next(&self, at: usize, left: u32, open: u64, next: usize) -> Option<(u32, u32)> {
+        let distance = self.distances[at][next];
+        if open & 1 << next != 0 || distance == u32::MAX || distance + 1 >= left {
+            return None;
+        }
+        let left = left - distance - 1;
+        Some((left, self.flows[next] * left))
+    }
+
+    /// The most pressure one person can release in `minutes`, opening only
+    /// valves in `allowed`, and how.
+    pub fn plan(&self, minutes: u32, allowed: u64) -> Schedule {
+        let mut memo = Memo::new();
+        let (mut at, mut left, mut open) = (self.names.len(), minutes, !allowed);
+        let released = self.best_from(at, minutes, open, &mut memo);
+        // Follow the choices that lead to the best, all remembered by now
+        let mut openings = Vec::new();
+        let mut to_come = released;
+        while to_come > 0 {
+            let (next, after, gain) = (0..self.names.len())
+                .filter_map(|next| {
+                    let (after, gain) = self.open_next(at, left, open, next)?;
+                    let rest = self.best_from(next, after, open | 1 << next, &mut memo);
+                    (gain + rest == to_come).then_some((next, after, gain))
+                })
+                .next()
+                .unwrap();
+            openings.push(Opening {
+                valve: self.names[next].clone(),
+                minute: minutes - after,
+            });
+            (at, left, open, to_come) = (next, after, open | 1 << next, to_come - gain);
+        }
+        Schedule { released, openings }
+    }
+
+    /// The most pressure one person can release in `minutes`, by bitmask of
+    /// the valves they may open: whatever they open has to be among them.
+    pub fn best_per_subset(&self, minutes: u32) -> Result<Vec<u32>> {
+        let n = self.names.len();
+        if n > MAX_SUBSET_VALVES {
+            return Err(AocError::solve(format!(
+                "{} valves are worth opening, too many for a table of every subset",
+                n
+            )));
+        }
+        let mut best = vec![0; 1 << n];
+        self.search(minutes, self.all(), |open, released| {
+            best[open as usize] = best[open as usize].max(released);
+        });
+        // So far it's by what's opened exactly; let each subset have the
+        // best of the subsets inside it
+        for bit in 0..n {
+            for mask in 0..best.len() {
+                if mask & 1 << bit != 0 {
+                    best[mask] = best[mask].max(best[mask ^ 1 << bit]);
+                }
+            }
+        }
+        Ok(best)
+    }
+
+    /// The most pressure `agents` people can release working together for
+    /// `minutes` each, and how. No two open the same valve, so the best
+    /// team is the best way to share the valves out: adding each person
+    /// tries every way of splitting each subset between them and those
+    /// before them.
+    pub fn plan_team(&self, agents: usize, minutes: u32) -> Result<TeamPlan> {
+        if agents == 0 {
+            return Ok(TeamPlan::default());
+        }
+        let alone = self.best_per_subset(minutes)?;
+        let mut team = alone.clone();
+        // For each person after the first, what each subset left the others
+        let mut splits = Vec::new();
+        for _ in 1..agents {
+            let mut next = vec![0; team.len()];
+            let mut split = vec![0; team.len()];
+            for mask in 0..team.len() {
+                let mut others = mask;
+                loop {
+                    let released = team[others] + alone[mask ^ others];
+                    if released > next[mask] {
+                        next[mask] = released;
+                        split[mask] = others;
+                    }
+                    if others == 0 {
+                        break;
+                    }
+                    others = (others - 1) & mask;
+                }
+            }
+            team = next;
+            splits.push(split);
+        }
+
+        let mut mask = self.all() as usize;
+        let mut shares = Vec::new();
+        for split in splits.iter().rev() {
+            shares.push(mask ^ split[mask]);
+            mask = split[mask];
+        }
+        shares.push(mask);
+        shares.reverse();
+        Ok(TeamPlan {
+            released: team[self.all() as usize],
+            schedules: shares
+                .into_iter()
+                .map(|share| self.plan(minutes, share as u64))
+                .collect(),
+        })
+    }
+
+    /// Every valve worth opening, as a bitmask.
+    pub fn all(&self) -> u64 {
+        match self.names.len() {
+            64 => u64::MAX,
+            n => (1 << n) - 1,
+        }
+    }
 }
 
-fn part2(input: &str) -> usize {
-    input.len()
+/// The colours routes are drawn in, one per member of a team.
+const ROUTE_COLOURS: [&str; 4] = ["red", "blue", "darkgreen", "darkorange"];
+
+/// The tunnels as an undirected Graphviz DOT graph, to render with `dot`.
+/// Valves are labelled with their flow rates, those with none drawn dashed
+/// and grey, and the start with a double border. Each schedule's walk from
+/// `start` is drawn over the tunnels in its own colour, every step labelled
+/// with the minute it's taken in and every valve it opens with the minute
+/// it's opened in.
+pub fn dot(valves: &ValveGraph, start: &str, schedules: &[Schedule]) -> Result<String> {
+    let index = |name: &str| {
+        valves
+            .index(name)
+            .ok_or_else(|| AocError::solve(format!("there's no valve `{}`", name)))
+    };
+    let start = index(start)?;
+    let mut opened = vec![None; valves.len()];
+    let mut steps = Vec::new();
+    for (schedule, colour) in schedules.iter().zip(ROUTE_COLOURS.iter().cycle()) {
+        let (mut at, mut minute) = (start, 0);
+        for opening in &schedule.openings {
+            let valve = index(&opening.valve)?;
+            let walk = search::bfs([at], |&v| valves.tunnels(v).to_vec(), |&v| v == valve)
+                .ok_or_else(|| AocError::solve(format!("`{}` can't be reached", opening.valve)))?;
+            for (step, pair) in walk.path.windows(2).enumerate() {
+                steps.push((pair[0], pair[1], minute + 1 + step, colour));
+            }
+            opened[valve] = Some((opening.minute, colour));
+            (at, minute) = (valve, opening.minute as usize);
+        }
+    }
+
+    let mut out = "graph valves {\n".to_string();
+    for (valve, &opened) in opened.iter().enumerate() {
+        let mut label = format!("{}\\nflow {}", valves.name(valve), valves.flow(valve));
+        let mut attributes = Vec::new();
+        if valves.flow(valve) == 0 {
+            attributes.push("style=dashed, color=grey60, fontcolor=grey40".to_string());
+        }
+        if valve == start {
+            attributes.push("peripheries=2".to_string());
+        }
+        if let Some((minute, colour)) = opened {
+            write!(label, "\\nopened minute {}", minute).unwrap();
+            attributes.push(format!("color={}, penwidth=2", colour));
+        }
+        attributes.insert(0, format!("label=\"{}\"", label));
+        writeln!(
+            out,
+            "  \"{}\" [{}];",
+            valves.name(valve),
+            attributes.join(", ")
+        )
+        .unwrap();
+    }
+    for valve in 0..valves.len() {
+        for &to in valves.tunnels(valve) {
+            // Once for each pair, however many ways it's listed
+            if valve < to || (valve > to && !valves.tunnels(to).contains(&valve)) {
+                writeln!(
+                    out,
+                    "  \"{}\" -- \"{}\";",
+                    valves.name(valve),
+                    valves.name(to)
+                )
+                .unwrap();
+            }
+        }
+    }
+    for (from, to, minute, colour) in steps {
+        writeln!(
+            out,
+            "  \"{}\" -- \"{}\" [color={}, fontcolor={}, penwidth=2, label=\"{}\"];",
+            valves.name(from),
+            valves.name(to),
+            colour,
+            colour,
+            minute
+        )
+        .unwrap();
+    }
+    out.push_str("}\n");
+    Ok(out)
 }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = ValveGraph;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<ValveGraph> {
+        let valves = parse_all(
+            input,
+            "`Valve <name> has flow rate=<n>; tunnels lead to valves <name>, ...`",
+            separated_list1(line_ending, valve)(input),
+        )?;
+        ValveGraph::new(valves)
+    }
+
+    fn part1(&self, valves: &ValveGraph) -> Result<u32> {
+        let network = Network::new(valves, START)?;
+        Ok(network.plan(self.minutes, network.all()).released)
+    }
+
+    fn part2(&self, valves: &ValveGraph) -> Result<u32> {
+        let network = Network::new(valves, START)?;
+        Ok(network.plan_team(self.agents, self.team_minutes)?.released)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day16_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 1651);
+        assert_eq!(
+            Day16::default()
+                .part1(&Day16::default().parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            1651
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 8);
+        assert_eq!(
+            Day16::default()
+                .part2(&Day16::default().parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            1707
+        );
+    }
+
+    fn example() -> ValveGraph {
+        Day16::default().parse(&normalise(TEST_INPUT)).unwrap()
+    }
+
+    #[test]
+    fn parser_works() {
+        let valves = example();
+        assert_eq!(valves.len(), 10);
+        let hh = valves.index("HH").unwrap();
+        assert_eq!((valves.name(hh), valves.flow(hh)), ("HH", 22));
+        assert_eq!(valves.tunnels(hh), [valves.index("GG").unwrap()]);
+        assert_eq!(valves.index("ZZ"), None);
+
+        // Names needn't be two capitals
+        let pumps = "Valve START has flow rate=0; tunnel leads to valve Pump2\n\
+                     Valve Pump2 has flow rate=17; tunnels lead to valves START";
+        let pumps = Day16::default().parse(pumps).unwrap();
+        assert_eq!(pumps.flow(pumps.index("Pump2").unwrap()), 17);
+        assert_eq!(pumps.tunnels(0), [1]);
+
+        let error = |input: &str| Day16::default().parse(input).unwrap_err().to_string();
+        let first = "Valve AA has flow rate=0; tunnel leads to valve AA\n";
+        assert!(error(&format!(
+            "{}Valve BB has flow rate=x; tunnel leads to valve AA",
+            first
+        ))
+        .starts_with("input:2: expected `Valve <name>"));
+        assert_eq!(
+            error(&format!(
+                "{}Valve BB has flow rate=1; tunnels lead to valves AA, x",
+                first
+            )),
+            "no answer: `BB` leads to no valve `x`"
+        );
+        assert!(error(&format!(
+            "{}Valve AA has flow rate=1; tunnel leads to valve AA",
+            first
+        ))
+        .contains("listed twice"));
+    }
+
+    #[test]
+    fn plans_work() {
+        let network = Network::new(&example(), START).unwrap();
+        assert_eq!(network.names().len(), 6);
+        let best = network.plan(30, network.all());
+        assert_eq!(best.released, 1651);
+        let opened = best
+            .openings
+            .iter()
+            .map(|o| (o.valve.as_str(), o.minute))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            opened,
+            [
+                ("DD", 2),
+                ("BB", 5),
+                ("JJ", 9),
+                ("HH", 17),
+                ("EE", 21),
+                ("CC", 24)
+            ]
+        );
+
+        // Without DD the best order changes
+        let dd = network.names().iter().position(|n| n == "DD").unwrap();
+        let without_dd = network.plan(30, network.all() & !(1 << dd));
+        assert!(without_dd.released < 1651);
+        assert!(without_dd.openings.iter().all(|o| o.valve != "DD"));
+        assert_eq!(network.plan(1, network.all()), Schedule::default());
+        assert!(Network::new(&example(), "ZZ").is_err());
+    }
+
+    #[test]
+    fn teams_work() {
+        let network = Network::new(&example(), START).unwrap();
+        let pair = network.plan_team(2, 26).unwrap();
+        assert_eq!(pair.released, 1707);
+        assert_eq!(pair.schedules.len(), 2);
+        assert_eq!(pair.schedules.iter().map(|s| s.released).sum::<u32>(), 1707);
+        let mut opened = pair
+            .schedules
+            .iter()
+            .flat_map(|s| s.openings.iter().map(|o| o.valve.as_str()))
+            .collect::<Vec<_>>();
+        opened.sort();
+        assert_eq!(opened, ["BB", "CC", "DD", "EE", "HH", "JJ"]);
+
+        let best = network.best_per_subset(26).unwrap();
+        assert_eq!(best.len(), 64);
+        assert!(best[1..].iter().all(|&b| b > 0));
+        assert_eq!(network.plan_team(1, 30).unwrap().released, 1651);
+        assert_eq!(network.plan_team(0, 30).unwrap(), TeamPlan::default());
+        // A chain of more valves than the table can take
+        let chain = (0..=MAX_SUBSET_VALVES)
+            .map(|i| {
+                format!(
+                    "Valve V{} has flow rate=1; tunnel leads to valve V{}",
+                    i,
+                    i + 1
+                )
+            })
+            .chain(["Valve V17 has flow rate=0; tunnel leads to valve V0".to_string()])
+            .collect::<Vec<_>>()
+            .join("\n");
+        let chain = Network::new(&Day16::default().parse(&chain).unwrap(), "V0").unwrap();
+        assert_eq!(chain.names().len(), MAX_SUBSET_VALVES + 1);
+        assert!(chain.plan_team(2, 26).is_err());
+        assert_eq!(chain.plan(12, chain.all()).openings.len(), 6);
+
+        let trio = network.plan_team(3, 26).unwrap();
+        assert!(trio.released >= 1707);
+        assert_eq!(trio.schedules.len(), 3);
+    }
+
+    #[test]
+    fn dot_works() {
+        let valves = example();
+        let network = Network::new(&valves, START).unwrap();
+        let best = network.plan(30, network.all());
+        let graph = dot(&valves, START, &[best]).unwrap();
+        assert!(graph.starts_with("graph valves {\n"));
+        assert!(graph.ends_with("}\n"));
+        assert!(graph.contains(
+            "  \"AA\" [label=\"AA\\nflow 0\", style=dashed, color=grey60, fontcolor=grey40, peripheries=2];"
+        ));
+        assert!(graph.contains(
+            "  \"DD\" [label=\"DD\\nflow 20\\nopened minute 2\", color=red, penwidth=2];"
+        ));
+        assert!(graph
+            .contains("  \"AA\" -- \"DD\" [color=red, fontcolor=red, penwidth=2, label=\"1\"];"));
+        // Each tunnel once, and a step for every minute spent walking
+        assert_eq!(graph.matches("-- ").count(), 10 + 18);
+        assert_eq!(graph.matches("color=red, fontcolor").count(), 18);
+
+        let team = network.plan_team(2, 26).unwrap();
+        let graph = dot(&valves, START, &team.schedules).unwrap();
+        assert!(graph.contains("color=red") && graph.contains("color=blue"));
+        assert!(dot(&valves, "ZZ", &[]).is_err());
     }
 }
@@ -1,31 +1,342 @@
-const INPUT: &str = include_str!("../data/Day17.txt");
+use crate::{
+    error::{AocError, Result},
+    Solution,
+};
 
-fn part1(input: &str) -> usize {
-    input.len()
+/// The chamber is `width` wide; each rock appears `spawn` from the floor or
+/// the tower, and part 1 drops `rocks` of them, taking `shapes` in turn.
+pub struct Day17 {
+    pub shapes: Vec<Shape>,
+    pub width: usize,
+    pub spawn: Spawn,
+    pub rocks: usize,
 }
 
-fn part2(input: &str) -> usize {
-    input.len()
+/// The puzzle's chamber and rocks.
+impl Default for Day17 {
+    fn default() -> Self {
+        Day17 {
+            shapes: Shape::puzzle(),
+            width: 7,
+            spawn: Spawn::default(),
+            rocks: 2022,
+        }
+    }
+}
+
+/// Where a new rock appears: its left edge `left` cells from the wall, and
+/// its bottom edge `above` empty rows over the floor or the highest rock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spawn {
+    pub left: usize,
+    pub above: usize,
+}
+
+impl Default for Spawn {
+    fn default() -> Self {
+        Spawn { left: 2, above: 3 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// A row of the chamber, a bit to each cell with the leftmost the lowest.
+type Row = u64;
+
+/// The widest chamber a `Row` can hold.
+pub const MAX_WIDTH: usize = Row::BITS as usize;
+
+/// A rock, as rows from the bottom up, each placed against the left wall.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    rows: Vec<Row>,
+    width: usize,
 }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+impl Shape {
+    /// A shape drawn the way the puzzle does, top row first, with `#` for
+    /// rock and `.` for air. Rows of only air above or below the rock are
+    /// dropped, as they'd only pad out the tower.
+    pub fn parse(picture: &str) -> Result<Shape> {
+        let mut rows = Vec::new();
+        let mut width = 0;
+        for line in picture.lines().rev() {
+            let mut row: Row = 0;
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' | '.' if x >= MAX_WIDTH => {
+                        return Err(AocError::parse(
+                            line,
+                            &format!("a row at most {} wide", MAX_WIDTH),
+                        ))
+                    }
+                    '#' => row |= 1 << x,
+                    '.' => {}
+                    _ => return Err(AocError::parse(line, "a row of `#`s and `.`s")),
+                }
+            }
+            width = width.max(Row::BITS - row.leading_zeros());
+            rows.push(row);
+        }
+        if width == 0 {
+            return Err(AocError::parse(picture, "a shape with some rock in it"));
+        }
+        while rows.last() == Some(&0) {
+            rows.pop();
+        }
+        let below = rows.iter().take_while(|&&row| row == 0).count();
+        rows.drain(..below);
+        Ok(Shape {
+            rows,
+            width: width as usize,
+        })
+    }
+
+    /// The five rocks from the puzzle, in the order they fall.
+    pub fn puzzle() -> Vec<Shape> {
+        [
+            "####",
+            ".#.\n###\n.#.",
+            "..#\n..#\n###",
+            "#\n#\n#\n#",
+            "##\n##",
+        ]
+        .into_iter()
+        .map(|picture| Shape::parse(picture).unwrap())
+        .collect()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+/// The chamber rocks fall into, blown about by jets of gas.
+pub struct Chamber<'a> {
+    width: usize,
+    spawn: Spawn,
+    shapes: &'a [Shape],
+    jets: &'a [Jet],
+    /// Settled rock, from the floor up. Only rows with rock in them are
+    /// kept, so this is as tall as the tower.
+    rows: Vec<Row>,
+    rocks: usize,
+    next_jet: usize,
+}
+
+impl<'a> Chamber<'a> {
+    /// An empty chamber, as long as every shape fits in it where it spawns.
+    pub fn new(width: usize, spawn: Spawn, shapes: &'a [Shape], jets: &'a [Jet]) -> Result<Self> {
+        if width > MAX_WIDTH {
+            return Err(AocError::solve(format!(
+                "the chamber is {} wide, but can be at most {}",
+                width, MAX_WIDTH
+            )));
+        }
+        if shapes.is_empty() || jets.is_empty() {
+            return Err(AocError::solve("there need to be rocks and jets"));
+        }
+        if let Some(shape) = shapes.iter().find(|s| spawn.left + s.width > width) {
+            return Err(AocError::solve(format!(
+                "a rock {} wide doesn't fit {} from the wall of a chamber {} wide",
+                shape.width, spawn.left, width
+            )));
+        }
+        Ok(Chamber {
+            width,
+            spawn,
+            shapes,
+            jets,
+            rows: Vec::new(),
+            rocks: 0,
+            next_jet: 0,
+        })
+    }
+
+    /// How tall the tower is.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether `shape`, `x` from the left wall with its bottom at row `y`,
+    /// is clear of the walls and the rock already there.
+    fn fits(&self, shape: &Shape, x: usize, y: usize) -> bool {
+        x + shape.width <= self.width
+            && shape
+                .rows
+                .iter()
+                .zip(self.rows.iter().skip(y))
+                .all(|(&rock, &row)| rock << x & row == 0)
+    }
+
+    /// Drop the next rock, letting the jets push it until it comes to rest.
+    pub fn drop_rock(&mut self) {
+        let shape = &self.shapes[self.rocks % self.shapes.len()];
+        let (mut x, mut y) = (self.spawn.left, self.height() + self.spawn.above);
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+            let pushed = match jet {
+                Jet::Left => x.checked_sub(1),
+                Jet::Right => Some(x + 1),
+            };
+            if let Some(pushed) = pushed.filter(|&p| self.fits(shape, p, y)) {
+                x = pushed;
+            }
+            if y == 0 || !self.fits(shape, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (i, &rock) in shape.rows.iter().enumerate() {
+            if y + i == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y + i] |= rock << x;
+        }
+        self.rocks += 1;
+    }
+
+    /// The tower drawn the way the puzzle does, top row first.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for &row in self.rows.iter().rev() {
+            out.push('|');
+            out.extend((0..self.width).map(|x| if row >> x & 1 == 1 { '#' } else { '.' }));
+            out.push_str("|\n");
+        }
+        out.push('+');
+        out.push_str(&"-".repeat(self.width));
+        out.push_str("+\n");
+        out
+    }
+}
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+
+    type Input = Vec<Jet>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Jet>> {
+        let jets = input
+            .chars()
+            .enumerate()
+            .map(|(x, c)| match c {
+                '<' => Ok(Jet::Left),
+                '>' => Ok(Jet::Right),
+                _ => Err(AocError::parse(
+                    &c.to_string(),
+                    &format!("`<` or `>` in column {}", x + 1),
+                )
+                .at_line(1)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if jets.is_empty() {
+            return Err(AocError::parse(input, "some jets").at_line(1));
+        }
+        Ok(jets)
+    }
+
+    fn part1(&self, jets: &Vec<Jet>) -> Result<usize> {
+        let mut chamber = Chamber::new(self.width, self.spawn, &self.shapes, jets)?;
+        for _ in 0..self.rocks {
+            chamber.drop_rock();
+        }
+        Ok(chamber.height())
+    }
+
+    fn part2(&self, input: &Vec<Jet>) -> Result<usize> {
+        Ok(input.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day17_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 3068);
+        assert_eq!(
+            Day17::default()
+                .part1(&Day17::default().parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            3068
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 8);
+        assert_eq!(
+            Day17::default()
+                .part2(&Day17::default().parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            8
+        );
+    }
+
+    #[test]
+    fn chamber_works() {
+        let jets = Day17::default().parse(&normalise(TEST_INPUT)).unwrap();
+        let shapes = Shape::puzzle();
+        assert_eq!((shapes[1].width(), shapes[1].height()), (3, 3));
+        let mut chamber = Chamber::new(7, Spawn::default(), &shapes, &jets).unwrap();
+        for _ in 0..3 {
+            chamber.drop_rock();
+        }
+        assert_eq!(
+            chamber.render(),
+            "|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+
+"
+        );
+
+        // A single bar drops straight down a chamber only as wide as it
+        let bar = [Shape::parse("####").unwrap()];
+        let spawn = Spawn { left: 0, above: 0 };
+        let mut chamber = Chamber::new(4, spawn, &bar, &jets).unwrap();
+        chamber.drop_rock();
+        chamber.drop_rock();
+        assert_eq!(chamber.render(), "|####|\n|####|\n+----+\n");
+
+        // Air around a shape doesn't add to the tower
+        let padded = [Shape::parse("..\n##\n..").unwrap()];
+        assert_eq!(padded[0], Shape::parse("##").unwrap());
+        let mut chamber = Chamber::new(2, spawn, &padded, &jets).unwrap();
+        chamber.drop_rock();
+        chamber.drop_rock();
+        assert_eq!(chamber.height(), 2);
+
+        let day = Day17 {
+            width: 9,
+            rocks: 100,
+            ..Day17::default()
+        };
+        assert!(day.part1(&jets).unwrap() < 100 * 13 / 5);
+        assert!(Chamber::new(3, Spawn::default(), &shapes, &jets).is_err());
+        assert!(Chamber::new(65, Spawn::default(), &shapes, &jets).is_err());
+        assert!(Shape::parse("..\n..").is_err());
+        assert!(Shape::parse(&".".repeat(MAX_WIDTH + 1)).is_err());
+        assert_eq!(
+            Day17::default().parse("<>x<").unwrap_err().to_string(),
+            "input:1: expected `<` or `>` in column 3, found \"x\""
+        );
     }
 }
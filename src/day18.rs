@@ -1,31 +1,51 @@
-const INPUT: &str = include_str!("../data/Day18.txt");
+use crate::{error::Result, Solution};
 
-fn part1(input: &str) -> usize {
-    input.len()
-}
+pub struct Day18;
 
-fn part2(input: &str) -> usize {
-    input.len()
-}
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Result<usize> {
+        Ok(input.len())
+    }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+    fn part2(&self, input: &String) -> Result<usize> {
+        Ok(input.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day18_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 64);
+        assert_eq!(
+            Day18
+                .part1(&Day18.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            64
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 8);
+        assert_eq!(
+            Day18
+                .part2(&Day18.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            8
+        );
     }
 }
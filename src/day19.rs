@@ -1,31 +1,51 @@
-const INPUT: &str = include_str!("../data/Day19.txt");
+use crate::{error::Result, Solution};
 
-fn part1(input: &str) -> usize {
-    input.len()
-}
+pub struct Day19;
 
-fn part2(input: &str) -> usize {
-    input.len()
-}
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Result<usize> {
+        Ok(input.len())
+    }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+    fn part2(&self, input: &String) -> Result<usize> {
+        Ok(input.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day19_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 33);
+        assert_eq!(
+            Day19
+                .part1(&Day19.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            33
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 8);
+        assert_eq!(
+            Day19
+                .part2(&Day19.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            8
+        );
     }
 }
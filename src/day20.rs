@@ -1,31 +1,51 @@
-const INPUT: &str = include_str!("../data/Day20.txt");
+use crate::{error::Result, Solution};
 
-fn part1(input: &str) -> usize {
-    input.len()
-}
+pub struct Day20;
 
-fn part2(input: &str) -> usize {
-    input.len()
-}
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &String) -> Result<usize> {
+        Ok(input.len())
+    }
 
-fn main() {
-    println!("Part 1: {}", part1(INPUT));
-    println!("Part 2: {}", part2(INPUT));
+    fn part2(&self, input: &String) -> Result<usize> {
+        Ok(input.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::normalise;
 
     const TEST_INPUT: &str = include_str!("../data/Day20_test.txt");
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1(TEST_INPUT), 3);
+        assert_eq!(
+            Day20
+                .part1(&Day20.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            3
+        );
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2(TEST_INPUT), 8);
+        assert_eq!(
+            Day20
+                .part2(&Day20.parse(&normalise(TEST_INPUT)).unwrap())
+                .unwrap(),
+            8
+        );
     }
 }
@@ -0,0 +1,169 @@
+use std::{fmt::Display, io, path::PathBuf, process::ExitCode};
+
+#[derive(Debug)]
+pub enum AocError {
+    /// An input file couldn't be read.
+    Io { path: PathBuf, source: io::Error },
+    /// A day's input, or a variant of it, isn't in the cache.
+    MissingInput {
+        day: u32,
+        variant: Option<String>,
+        path: PathBuf,
+    },
+    /// Downloading an input failed.
+    Fetch { day: Option<u32>, message: String },
+    /// Some input didn't have the shape a parser expected. `line` is 1-based,
+    /// with 0 meaning "not known yet"; `file` is filled in by whoever loaded
+    /// the input.
+    Parse {
+        file: Option<PathBuf>,
+        line: usize,
+        text: String,
+        expected: String,
+    },
+    /// The input parsed, but doesn't have an answer.
+    Solve(String),
+    /// A part panicked rather than returning an error.
+    Panic(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(text: &str, expected: &str) -> AocError {
+        AocError::Parse {
+            file: None,
+            line: 0,
+            text: text.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    pub fn solve(message: impl Display) -> AocError {
+        AocError::Solve(message.to_string())
+    }
+
+    /// Record which line a parse error came from, unless it already knows.
+    pub fn at_line(self, n: usize) -> AocError {
+        match self {
+            AocError::Parse {
+                file,
+                line: 0,
+                text,
+                expected,
+            } => AocError::Parse {
+                file,
+                line: n,
+                text,
+                expected,
+            },
+            e => e,
+        }
+    }
+
+    /// Record which file a parse error came from.
+    pub fn in_file(self, path: impl Into<PathBuf>) -> AocError {
+        match self {
+            AocError::Parse {
+                file: None,
+                line,
+                text,
+                expected,
+            } => AocError::Parse {
+                file: Some(path.into()),
+                line,
+                text,
+                expected,
+            },
+            e => e,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            AocError::MissingInput {
+                day,
+                variant: Some(variant),
+                path,
+            } => write!(
+                f,
+                "no {} input for day {}: {} doesn't exist",
+                variant,
+                day,
+                path.display()
+            ),
+            AocError::MissingInput {
+                day,
+                variant: None,
+                path,
+            } => write!(
+                f,
+                "no input for day {}: {} doesn't exist (`aoc fetch {}` downloads it)",
+                day,
+                path.display(),
+                day
+            ),
+            AocError::Fetch {
+                day: Some(day),
+                message,
+            } => write!(f, "couldn't fetch day {}: {}", day, message),
+            AocError::Fetch { day: None, message } => write!(f, "couldn't fetch: {}", message),
+            AocError::Parse {
+                file,
+                line,
+                text,
+                expected,
+            } => {
+                match file {
+                    Some(file) => write!(f, "{}:", file.display())?,
+                    None => write!(f, "input:")?,
+                }
+                if *line > 0 {
+                    write!(f, "{}:", line)?;
+                }
+                write!(f, " expected {}, found {:?}", expected, text)
+            }
+            AocError::Solve(message) => write!(f, "no answer: {}", message),
+            AocError::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Turn a binary's result into its exit code, printing any error.
+pub fn report(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        let e = AocError::parse("1-2,x", "`a-b,c-d`");
+        assert_eq!(e.to_string(), "input: expected `a-b,c-d`, found \"1-2,x\"");
+        let e = e.at_line(3).at_line(4).in_file("data/Day04.txt");
+        assert_eq!(
+            e.to_string(),
+            "data/Day04.txt:3: expected `a-b,c-d`, found \"1-2,x\""
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::process::ExitCode;
+
+use crate::{
+    answers::{self, Verdict},
+    cache::Cache,
+    error::{AocError, Result},
+    input, runner,
+};
+
+pub const PATH: &str = "data/examples.toml";
+
+pub const USAGE: &str = "usage: aoc examples [<day>|<from>..<to>|<from>..=<to>|--all]";
+
+/// A named example from a puzzle, with whichever answers it gives. The
+/// input is the cache variant of the same name (`data/Day09_test2.txt`), and
+/// the answers are kept in `examples.toml`, a table per example:
+///
+/// ```toml
+/// [day09.test2]
+/// part2 = 36
+/// ```
+///
+/// so adding an example needs no new code.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub day: u32,
+    pub name: String,
+    pub expected: [Option<String>; 2],
+}
+
+pub fn parse(text: &str) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for (key, value) in &answers::parse_toml(text)? {
+        let (day, table) = answers::day_table(key, value)?;
+        for (name, parts) in table {
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| AocError::parse(name, "a `[dayNN.<example>]` table"))?;
+            let mut expected = [None, None];
+            for (part, answer) in answers::parts(parts)? {
+                expected[part as usize - 1] = Some(answer);
+            }
+            examples.push(Example {
+                day,
+                name: name.clone(),
+                expected,
+            });
+        }
+    }
+    examples.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(examples)
+}
+
+pub fn load(path: &str) -> Result<Vec<Example>> {
+    input::load(path).and_then(|text| parse(&text).map_err(|e| e.in_file(path)))
+}
+
+/// Run an example through its day in the registry. Parts the example has
+/// no answer for are `Missing`.
+pub fn check(example: &Example, cache: &Cache) -> [Verdict; 2] {
+    let error = |e: String| [Verdict::Error(e.clone()), Verdict::Error(e)];
+    let day = match runner::days().into_iter().find(|d| d.day() == example.day) {
+        Some(day) => day.for_examples().unwrap_or(day),
+        None => return error(format!("day {} is not in the registry", example.day)),
+    };
+    let solved = cache
+        .resolve(example.day, Some(&example.name))
+        .and_then(|path| {
+            let name = path.display().to_string();
+            input::load(&path)
+                .and_then(|input| day.solve(&input))
+                .map_err(|e| e.in_file(name))
+        });
+    let (part1, part2) = match solved {
+        Ok(parts) => parts,
+        Err(e) => return error(e.to_string()),
+    };
+    let [expected1, expected2] = &example.expected;
+    let check = |expected: &Option<String>, actual: Result<String>| match expected {
+        None => Verdict::Missing,
+        Some(expected) => answers::check(Some(expected), &actual.map_err(|e| e.to_string())),
+    };
+    [check(expected1, part1), check(expected2, part2)]
+}
+
+/// The `aoc examples` command: check each selected day's examples, every
+/// day's by default.
+pub fn main(args: &[String]) -> ExitCode {
+    let all = ["--all".to_string()];
+    let args = if args.is_empty() { &all[..] } else { args };
+    let days = match runner::select(args, None) {
+        Ok(days) => days.iter().map(|(d, _)| d.day()).collect::<Vec<_>>(),
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let examples = match load(PATH) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let cache = Cache::default();
+    let mut failed = false;
+    println!("Day | Example | Part | Result");
+    println!("----+---------+------+-------");
+    for example in examples.iter().filter(|e| days.contains(&e.day)) {
+        for (part, verdict) in (1..).zip(check(example, &cache)) {
+            if verdict == Verdict::Missing {
+                continue;
+            }
+            failed |= verdict != Verdict::Pass;
+            println!(
+                "{:>3} | {:7} | {:>4} | {}",
+                example.day,
+                example.name,
+                part,
+                answers::describe(&verdict)
+            );
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let examples =
+            parse("[day09.test2]\npart2 = 36\n\n[day09.test]\npart1 = 13\npart2 = 1").unwrap();
+        assert_eq!(
+            examples,
+            [
+                Example {
+                    day: 9,
+                    name: "test".to_string(),
+                    expected: [Some("13".to_string()), Some("1".to_string())]
+                },
+                Example {
+                    day: 9,
+                    name: "test2".to_string(),
+                    expected: [None, Some("36".to_string())]
+                }
+            ]
+        );
+        assert!(parse("[day09]\npart1 = 13").is_err());
+    }
+}
@@ -0,0 +1,453 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// What a point's coordinates can be.
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {
+        $(impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs_diff(self, other: Self) -> Self {
+                if self > other {
+                    self - other
+                } else {
+                    other - self
+                }
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A point, or a vector between points, on a plane. `y` grows downwards, as
+/// it does in puzzle diagrams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point, or a vector between points, in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub const ORIGIN: Point2<T> = Point2 {
+        x: T::ZERO,
+        y: T::ZERO,
+    };
+
+    pub fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance when diagonal steps count as one, as a king moves.
+    pub fn chebyshev(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    pub fn euclidean(self, other: Point2<T>) -> f64 {
+        let (dx, dy) = (self.x.abs_diff(other.x), self.y.abs_diff(other.y));
+        (dx * dx + dy * dy).to_f64().sqrt()
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point2<T> {
+    /// A quarter turn clockwise, as seen on screen.
+    pub fn turn_right(self) -> Point2<T> {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// A quarter turn anticlockwise, as seen on screen.
+    pub fn turn_left(self) -> Point2<T> {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// Each coordinate clamped to -1, 0 or 1: one step towards where this
+    /// vector points.
+    pub fn signum(self) -> Point2<T> {
+        let sign = |v: T| v.cmp(&T::ZERO) as i8;
+        let unit = |s: i8| match s {
+            -1 => -T::ONE,
+            0 => T::ZERO,
+            _ => T::ONE,
+        };
+        Point2::new(unit(sign(self.x)), unit(sign(self.y)))
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub const ORIGIN: Point3<T> = Point3 {
+        x: T::ZERO,
+        y: T::ZERO,
+        z: T::ZERO,
+    };
+
+    pub fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    pub fn euclidean(self, other: Point3<T>) -> f64 {
+        let (dx, dy, dz) = (
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        );
+        (dx * dx + dy * dy + dz * dz).to_f64().sqrt()
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point3<T> {
+    /// The six unit steps to the points sharing a face with this one's cube.
+    pub fn faces() -> [Point3<T>; 6] {
+        let (o, z) = (T::ONE, T::ZERO);
+        [
+            Point3::new(o, z, z),
+            Point3::new(-o, z, z),
+            Point3::new(z, o, z),
+            Point3::new(z, -o, z),
+            Point3::new(z, z, o),
+            Point3::new(z, z, -o),
+        ]
+    }
+
+    /// A quarter turn about the x axis, taking y towards z.
+    pub fn rotate_x(self) -> Point3<T> {
+        Point3::new(self.x, -self.z, self.y)
+    }
+
+    /// A quarter turn about the y axis, taking z towards x.
+    pub fn rotate_y(self) -> Point3<T> {
+        Point3::new(self.z, self.y, -self.x)
+    }
+
+    /// A quarter turn about the z axis, taking x towards y.
+    pub fn rotate_z(self) -> Point3<T> {
+        Point3::new(-self.y, self.x, self.z)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+macro_rules! ops {
+    ($p:ident { $($c:ident),* }) => {
+        impl<T: Coord> Add for $p<T> {
+            type Output = $p<T>;
+
+            fn add(self, other: $p<T>) -> $p<T> {
+                $p { $($c: self.$c + other.$c),* }
+            }
+        }
+
+        impl<T: Coord> Sub for $p<T> {
+            type Output = $p<T>;
+
+            fn sub(self, other: $p<T>) -> $p<T> {
+                $p { $($c: self.$c - other.$c),* }
+            }
+        }
+
+        impl<T: Coord> Mul<T> for $p<T> {
+            type Output = $p<T>;
+
+            fn mul(self, k: T) -> $p<T> {
+                $p { $($c: self.$c * k),* }
+            }
+        }
+
+        impl<T: Coord + Neg<Output = T>> Neg for $p<T> {
+            type Output = $p<T>;
+
+            fn neg(self) -> $p<T> {
+                $p { $($c: -self.$c),* }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $p<T> {
+            fn add_assign(&mut self, other: $p<T>) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $p<T> {
+            fn sub_assign(&mut self, other: $p<T>) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+ops!(Point2 { x, y });
+ops!(Point3 { x, y, z });
+
+/// The four ways along a grid's rows and columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// One step this way.
+    pub fn delta<T: Coord + Neg<Output = T>>(self) -> Point2<T> {
+        let (o, z) = (T::ONE, T::ZERO);
+        match self {
+            Direction::Up => Point2::new(z, -o),
+            Direction::Right => Point2::new(o, z),
+            Direction::Down => Point2::new(z, o),
+            Direction::Left => Point2::new(-o, z),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// The eight points of the compass, with north up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass {
+    pub const ALL: [Compass; 8] = [
+        Compass::N,
+        Compass::NE,
+        Compass::E,
+        Compass::SE,
+        Compass::S,
+        Compass::SW,
+        Compass::W,
+        Compass::NW,
+    ];
+
+    /// One step this way.
+    pub fn delta<T: Coord + Neg<Output = T>>(self) -> Point2<T> {
+        let (o, z) = (T::ONE, T::ZERO);
+        let (x, y) = match self {
+            Compass::N => (z, -o),
+            Compass::NE => (o, -o),
+            Compass::E => (o, z),
+            Compass::SE => (o, o),
+            Compass::S => (z, o),
+            Compass::SW => (-o, o),
+            Compass::W => (-o, z),
+            Compass::NW => (-o, -o),
+        };
+        Point2::new(x, y)
+    }
+}
+
+/// Operations on points that work axis by axis.
+pub trait Componentwise: Copy + Add<Output = Self> + Sub<Output = Self> {
+    /// A point with every coordinate one.
+    const ONES: Self;
+
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    /// Whether every coordinate is no more than `other`'s.
+    fn all_le(self, other: Self) -> bool;
+}
+
+macro_rules! componentwise {
+    ($p:ident { $($c:ident),* }) => {
+        impl<T: Coord> Componentwise for $p<T> {
+            const ONES: Self = $p { $($c: T::ONE),* };
+
+            fn min(self, other: Self) -> Self {
+                $p { $($c: self.$c.min(other.$c)),* }
+            }
+
+            fn max(self, other: Self) -> Self {
+                $p { $($c: self.$c.max(other.$c)),* }
+            }
+
+            fn all_le(self, other: Self) -> bool {
+                $(self.$c <= other.$c)&&*
+            }
+        }
+    };
+}
+
+componentwise!(Point2 { x, y });
+componentwise!(Point3 { x, y, z });
+
+/// The smallest box, edges included, holding some points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl<P: Componentwise> Bounds<P> {
+    pub fn of(points: impl IntoIterator<Item = P>) -> Option<Bounds<P>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(
+            Bounds {
+                min: first,
+                max: first,
+            },
+            |b, p| b.including(p),
+        ))
+    }
+
+    /// These bounds grown, if need be, to hold `p`.
+    pub fn including(self, p: P) -> Bounds<P> {
+        Bounds {
+            min: self.min.min(p),
+            max: self.max.max(p),
+        }
+    }
+
+    pub fn contains(&self, p: P) -> bool {
+        self.min.all_le(p) && p.all_le(self.max)
+    }
+
+    /// How many points wide the box is along each axis.
+    pub fn size(&self) -> P {
+        self.max - self.min + P::ONES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ops_work() {
+        let p = Point2::new(3, -2);
+        assert_eq!(p + Point2::new(1, 1), Point2::new(4, -1));
+        assert_eq!(p - Point2::new(1, 1), Point2::new(2, -3));
+        assert_eq!(p * 2, Point2::new(6, -4));
+        assert_eq!(-p, Point2::new(-3, 2));
+        assert_eq!(p.signum(), Point2::new(1, -1));
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn distances_work() {
+        let (a, b) = (Point2::new(0, 0), Point2::new(3, -4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(Point2::new(2_usize, 5).manhattan(Point2::new(4, 1)), 6);
+        assert_eq!(Point3::new(1, 1, 1).manhattan(Point3::ORIGIN), 3);
+    }
+
+    #[test]
+    fn rotations_work() {
+        let up = Direction::Up.delta::<i32>();
+        assert_eq!(up.turn_right(), Direction::Right.delta());
+        assert_eq!(up.turn_left(), Direction::Left.delta());
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p.rotate_z(), Point3::new(-2, 1, 3));
+        assert_eq!(p.rotate_x().rotate_x().rotate_x().rotate_x(), p);
+        assert_eq!(p.rotate_y().rotate_y(), Point3::new(-1, 2, -3));
+    }
+
+    #[test]
+    fn bounds_work() {
+        let b = Bounds::of([Point2::new(2, 5), Point2::new(-1, 3), Point2::new(4, 4)]).unwrap();
+        assert_eq!(b.min, Point2::new(-1, 3));
+        assert_eq!(b.max, Point2::new(4, 5));
+        assert_eq!(b.size(), Point2::new(6, 3));
+        assert!(b.contains(Point2::new(0, 4)));
+        assert!(!b.contains(Point2::new(0, 6)));
+        assert_eq!(Bounds::<Point2<i32>>::of([]), None);
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::{AocError, Result},
+    input::parse_lines,
+};
+
+/// A cell's position, as `(x, y)` from the top left.
+pub type Pos = (usize, usize);
+
+/// Steps to the four orthogonal neighbours: up, right, down, left.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Steps to all eight neighbours, clockwise from up.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parse a character map, one row per line, converting each character
+    /// with `f`. Rows must all be as wide as the first; `expected` describes a
+    /// cell for the error if one isn't, or if `f` rejects a character.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>> {
+        let width = input.lines().next().map_or(0, |s| s.chars().count());
+        let error =
+            |s: &str| AocError::parse(s, &format!("a row of {} {}", width.max(1), expected));
+        let rows = parse_lines(input, |s| {
+            match s.chars().map(&mut f).collect::<Option<Vec<_>>>() {
+                Some(row) if width > 0 && row.len() == width => Ok(row),
+                _ => Err(error(s)),
+            }
+        })?;
+        if rows.is_empty() {
+            return Err(error("").at_line(1));
+        }
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// One step from `pos` in direction `(dx, dy)`, if that's still on the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, that `pred` accepts.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The positions from `pos` (not including it) to the edge of the grid,
+    /// stepping by `(dx, dy)`.
+    pub fn ray(&self, pos: Pos, direction: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, direction), move |&p| self.step(p, direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, top first. A grid with no columns still has its rows,
+    /// each empty.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// The cells in column `x`, top first.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Like `map`, but `f` is also told where each cell is.
+    pub fn map_with_pos<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(p, c)| f(p, c)).collect(),
+        }
+    }
+
+    /// Combine two grids of the same size cell by cell.
+    pub fn zip<U, V>(&self, other: &Grid<U>, mut f: impl FnMut(&T, &U) -> V) -> Grid<V> {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).expect("position off the grid")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos).expect("position off the grid")
+    }
+}
+
+/// Each row on its own line, with the cells run together.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>> {
+        Grid::parse(input, "digits", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parse_works() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            digits("123\n45").unwrap_err().to_string(),
+            "input:2: expected a row of 3 digits, found \"45\""
+        );
+        assert!(digits("123\n4x6").is_err());
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn neighbours_work() {
+        let grid = digits("123\n456\n789").unwrap();
+        let values = |ps: Vec<Pos>| ps.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(values(grid.neighbours4((0, 0)).collect()), [2, 4]);
+        assert_eq!(values(grid.neighbours4((1, 1)).collect()), [2, 6, 8, 4]);
+        assert_eq!(values(grid.neighbours8((0, 0)).collect()), [2, 5, 4]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn lines_work() {
+        let grid = digits("123\n456\n789").unwrap();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6, 9]);
+        assert_eq!(
+            grid.ray((0, 2), (1, -1)).collect::<Vec<_>>(),
+            [(1, 1), (2, 0)]
+        );
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+
+        // No columns, but still rows
+        let empty = Grid::new(0, 2, 0);
+        assert_eq!(empty.rows().collect::<Vec<_>>(), [[], []]);
+        assert_eq!(empty.to_string(), "\n");
+        assert_eq!(Grid::new(3, 0, 0).column(1).count(), 0);
+    }
+
+    #[test]
+    fn map_and_zip_work() {
+        let grid = digits("12\n34").unwrap();
+        let doubled = grid.map(|d| d * 2);
+        assert_eq!(doubled.to_string(), "24\n68");
+        assert_eq!(grid.zip(&doubled, |a, b| a + b).to_string(), "36\n912");
+        assert_eq!(grid.find(|&d| d > 2), Some((0, 1)));
+    }
+}
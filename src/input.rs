@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use nom::IResult;
+
+use crate::error::{AocError, Result};
+
+/// Rewrite `\r\n` and lone `\r` line endings as `\n` and drop any trailing
+/// newlines, so parsers can split on `'\n'` whichever platform the input was
+/// saved on. Leading whitespace is significant (day 5's crate diagram) and is
+/// left alone.
+pub fn normalise(raw: &str) -> String {
+    raw.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// Read an input file and normalise it.
+pub fn load(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path)
+        .map(|s| normalise(&s))
+        .map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Read input from `source`, a file path or `-` for stdin.
+pub fn open(source: &str) -> Result<String> {
+    if source != "-" {
+        return load(source);
+    }
+    let mut raw = String::new();
+    io::stdin()
+        .read_to_string(&mut raw)
+        .map(|_| normalise(&raw))
+        .map_err(|source| AocError::Io {
+            path: "<stdin>".into(),
+            source,
+        })
+}
+
+/// How to refer to `source` in error messages.
+pub fn source_name(source: &str) -> &str {
+    if source == "-" {
+        "<stdin>"
+    } else {
+        source
+    }
+}
+
+/// Parse every line with `f`, tagging any error with its line number.
+pub fn parse_lines<T>(input: &str, f: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    let mut f = f;
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, line)| f(line).map_err(|e| e.at_line(n + 1)))
+        .collect()
+}
+
+/// The 1-based line `rest` starts on, where `rest` is a suffix of `input`.
+fn line_of(input: &str, rest: &str) -> usize {
+    input[..input.len() - rest.len()].matches('\n').count() + 1
+}
+
+/// Finish off a nom parse of the whole input: an error, or anything left
+/// over, is reported against the line the parser stopped on.
+pub fn parse_all<T>(input: &str, expected: &str, result: IResult<&str, T>) -> Result<T> {
+    let rest = match result {
+        Ok(("", value)) => return Ok(value),
+        Ok((rest, _)) => rest,
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => e.input,
+        Err(nom::Err::Incomplete(_)) => "",
+    };
+    // A list parser stops in front of the line ending before the bad line
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+    let text = rest.lines().next().unwrap_or("");
+    Err(AocError::parse(text, expected).at_line(line_of(input, rest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::line_ending, multi::separated_list1};
+
+    #[test]
+    fn normalise_works() {
+        assert_eq!(normalise("a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalise("a\rb\r"), "a\nb");
+        assert_eq!(normalise("a\nb\n\n"), "a\nb");
+        assert_eq!(normalise("    [D]\n"), "    [D]");
+    }
+
+    #[test]
+    fn parse_lines_reports_line() {
+        let result = parse_lines("1\n2\nx", |s| {
+            s.parse::<i32>().map_err(|_| AocError::parse(s, "a number"))
+        });
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "input:3: expected a number, found \"x\""
+        );
+    }
+
+    #[test]
+    fn parse_all_reports_line() {
+        let input = "ab\nab\nax";
+        let result = separated_list1(line_ending, tag("ab"))(input);
+        assert_eq!(
+            parse_all(input, "`ab`", result).unwrap_err().to_string(),
+            "input:3: expected `ab`, found \"ax\""
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use crate::grid::Grid;
+
+/// A colour, as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// `t` of the way from `from` to `to`.
+pub fn blend(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    [
+        mix(from[0], to[0]),
+        mix(from[1], to[1]),
+        mix(from[2], to[2]),
+    ]
+}
+
+/// A colour `t` of the way along a gradient through `stops`, spaced evenly.
+pub fn gradient(stops: &[Rgb], t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let i = (t.floor() as usize).min(stops.len() - 2);
+    blend(stops[i], stops[i + 1], t - i as f64)
+}
+
+/// A binary PPM image of `image`, each cell drawn as a `scale` pixel square.
+pub fn ppm(image: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let (width, height) = (image.width() * scale, image.height() * scale);
+    let mut out = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    for row in image.rows() {
+        let line = row
+            .iter()
+            .flat_map(|rgb| std::iter::repeat_n(rgb, scale))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        for _ in 0..scale {
+            out.extend_from_slice(&line);
+        }
+    }
+    out
+}
+
+/// `chars` drawn in black on `colours` with 24-bit ANSI escapes, for a
+/// terminal.
+pub fn ansi(chars: &Grid<char>, colours: &Grid<Rgb>) -> String {
+    let mut out = String::new();
+    for (y, row) in chars.rows().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            let [r, g, b] = colours[(x, y)];
+            write!(out, "\x1b[30;48;2;{};{};{}m{}", r, g, b, c).unwrap();
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours_work() {
+        assert_eq!(blend([0, 0, 0], [200, 100, 50], 0.5), [100, 50, 25]);
+        let stops = [[0, 0, 0], [100, 0, 0], [100, 100, 0]];
+        assert_eq!(gradient(&stops, 0.0), [0, 0, 0]);
+        assert_eq!(gradient(&stops, 0.25), [50, 0, 0]);
+        assert_eq!(gradient(&stops, 1.0), [100, 100, 0]);
+    }
+
+    #[test]
+    fn ppm_and_ansi_work() {
+        let image = Grid::new(2, 1, [1, 2, 3]);
+        let ppm = ppm(&image, 2);
+        assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 2 * 3);
+        assert_eq!(
+            ansi(&Grid::new(1, 1, 'x'), &Grid::new(1, 1, [1, 2, 3])),
+            "\x1b[30;48;2;1;2;3mx\x1b[0m\n"
+        );
+    }
+}
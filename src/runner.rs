@@ -0,0 +1,330 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bench::{self, Timings},
+    cache,
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    error::{AocError, Result},
+    Solution,
+};
+
+/// Object-safe view of a `Solution`, so days with different input and
+/// answer types can sit in the same registry.
+pub trait Runnable {
+    fn day(&self) -> u32;
+    /// Parse the input and run both parts. A parse failure fails the
+    /// whole day; otherwise each part succeeds or fails on its own.
+    fn solve(&self, input: &str) -> Result<(Result<String>, Result<String>)>;
+    /// Time parsing and each part separately over `iterations` runs.
+    fn bench(&self, input: &str, iterations: usize) -> Result<Timings>;
+    /// See `Solution::for_examples`.
+    fn for_examples(&self) -> Option<Box<dyn Runnable>>;
+}
+
+impl<S: Solution + 'static> Runnable for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn solve(&self, input: &str) -> Result<(Result<String>, Result<String>)> {
+        let parsed = catch(|| self.parse(input))?;
+        Ok((
+            catch(|| self.part1(&parsed).map(|a| a.to_string())),
+            catch(|| self.part2(&parsed).map(|a| a.to_string())),
+        ))
+    }
+
+    fn bench(&self, input: &str, iterations: usize) -> Result<Timings> {
+        catch(|| {
+            let parse = bench::time(iterations, || self.parse(input))?;
+            let parsed = self.parse(input)?;
+            Ok(Timings {
+                day: S::DAY,
+                parse,
+                part1: bench::time(iterations, || self.part1(&parsed))?,
+                part2: bench::time(iterations, || self.part2(&parsed))?,
+            })
+        })
+    }
+
+    fn for_examples(&self) -> Option<Box<dyn Runnable>> {
+        Solution::for_examples(self).map(|s| Box::new(s) as Box<dyn Runnable>)
+    }
+}
+
+pub fn days() -> Vec<Box<dyn Runnable>> {
+    vec![
+        Box::new(Day01),
+        Box::new(Day02),
+        Box::new(Day03),
+        Box::new(Day04),
+        Box::new(Day05),
+        Box::new(Day06),
+        Box::new(Day07),
+        Box::new(Day08),
+        Box::new(Day09),
+        Box::new(Day10),
+        Box::new(Day11),
+        Box::new(Day12::default()),
+        Box::new(Day13),
+        Box::new(Day14),
+        Box::new(Day15::default()),
+        Box::new(Day16::default()),
+        Box::new(Day17::default()),
+        Box::new(Day18),
+        Box::new(Day19),
+        Box::new(Day20),
+    ]
+}
+
+/// Parse a day selection: a single day (`7`), an exclusive range (`1..15`)
+/// or an inclusive range (`1..=15`).
+pub fn parse_selection(s: &str) -> std::result::Result<Vec<u32>, String> {
+    let day = |d: &str| {
+        d.trim()
+            .parse::<u32>()
+            .map_err(|_| format!("'{}' is not a day number", d))
+    };
+    if let Some((from, to)) = s.split_once("..=") {
+        Ok((day(from)?..=day(to)?).collect())
+    } else if let Some((from, to)) = s.split_once("..") {
+        Ok((day(from)?..day(to)?).collect())
+    } else {
+        Ok(vec![day(s)?])
+    }
+}
+
+/// Days paired with the input to run them on, if not their cached input.
+pub type Selected = Vec<(Box<dyn Runnable>, Option<String>)>;
+
+/// Resolve `--all`, or a list of selections, against the registry, pairing
+/// each day with `source` if given, which only makes sense for a single day.
+pub fn select(args: &[String], source: Option<String>) -> std::result::Result<Selected, String> {
+    let mut days = days();
+    let mut wanted = if args.iter().any(|a| a == "--all") {
+        days.iter().map(|d| d.day()).collect::<Vec<_>>()
+    } else {
+        let mut wanted = Vec::new();
+        for a in args {
+            wanted.extend(parse_selection(a)?);
+        }
+        wanted
+    };
+    wanted.sort_unstable();
+    wanted.dedup();
+    if wanted.is_empty() {
+        return Err("no days selected".to_string());
+    }
+    if source.is_some() && wanted.len() != 1 {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    wanted
+        .into_iter()
+        .map(|w| match days.iter().position(|d| d.day() == w) {
+            Some(i) => Ok((days.remove(i), source.clone())),
+            None => Err(format!("day {} is not in the registry", w)),
+        })
+        .collect()
+}
+
+/// How a day went. Errors are kept as their rendered message, since a parse
+/// failure is reported against both parts.
+pub struct Outcome {
+    pub day: u32,
+    pub part1: std::result::Result<String, String>,
+    pub part2: std::result::Result<String, String>,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    pub fn failed(&self) -> bool {
+        self.part1.is_err() || self.part2.is_err()
+    }
+}
+
+fn panic_message(e: Box<dyn Any + Send>) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|e| Err(AocError::Panic(panic_message(e))))
+}
+
+/// Run a day against `source`, a file path or `-` for stdin, or else its
+/// cached input.
+pub fn run(day: &dyn Runnable, source: Option<&str>) -> Outcome {
+    let start = Instant::now();
+    let solved = cache::open(day.day(), source).and_then(|(input, name)| {
+        let render = |e: AocError| e.in_file(&name).to_string();
+        day.solve(&input)
+            .map(|(part1, part2)| (part1.map_err(render), part2.map_err(render)))
+            .map_err(|e| e.in_file(&name))
+    });
+    let (part1, part2) = match solved {
+        Ok(parts) => parts,
+        Err(e) => {
+            let e = e.to_string();
+            (Err(e.clone()), Err(e))
+        }
+    };
+    Outcome {
+        day: day.day(),
+        part1,
+        part2,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Benchmark a day against `source`, a file path or `-` for stdin, or else
+/// its cached input.
+pub fn bench(day: &dyn Runnable, source: Option<&str>, iterations: usize) -> Result<Timings> {
+    let (input, name) = cache::open(day.day(), source)?;
+    day.bench(&input, iterations).map_err(|e| e.in_file(name))
+}
+
+fn cell(r: &std::result::Result<String, String>) -> Vec<String> {
+    match r {
+        Ok(s) => s.lines().map(|l| l.to_string()).collect(),
+        Err(e) => vec![format!("ERROR: {}", e)],
+    }
+}
+
+/// Lay the outcomes out as a table, one row per day. Multi-line answers
+/// (day 10's screen) spill onto continuation rows.
+pub fn table(outcomes: &[Outcome]) -> String {
+    let rows = outcomes
+        .iter()
+        .map(|o| {
+            (
+                o.day.to_string(),
+                cell(&o.part1),
+                cell(&o.part2),
+                format!("{:.1?}", o.elapsed),
+            )
+        })
+        .collect::<Vec<_>>();
+    let w1 = rows
+        .iter()
+        .flat_map(|r| r.1.iter().map(|s| s.len()))
+        .chain(std::iter::once("Part 1".len()))
+        .max()
+        .unwrap();
+    let w2 = rows
+        .iter()
+        .flat_map(|r| r.2.iter().map(|s| s.len()))
+        .chain(std::iter::once("Part 2".len()))
+        .max()
+        .unwrap();
+
+    let mut out = format!("Day | {:w1$} | {:w2$} | Time\n", "Part 1", "Part 2");
+    out += &format!("----+-{}-+-{}-+------\n", "-".repeat(w1), "-".repeat(w2));
+    for (day, p1, p2, time) in rows {
+        for line in 0..p1.len().max(p2.len()) {
+            let a = p1.get(line).map(|s| s.as_str()).unwrap_or("");
+            let b = p2.get(line).map(|s| s.as_str()).unwrap_or("");
+            if line == 0 {
+                out += &format!("{:>3} | {:w1$} | {:w2$} | {}\n", day, a, b, time);
+            } else {
+                out += &format!("    | {:w1$} | {:w2$} |\n", a, b);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_selection_works() {
+        assert_eq!(parse_selection("7"), Ok(vec![7]));
+        assert_eq!(parse_selection("1..4"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_selection("1..=4"), Ok(vec![1, 2, 3, 4]));
+        assert!(parse_selection("x").is_err());
+    }
+
+    #[test]
+    fn select_works() {
+        let args = |s: &str| {
+            s.split_whitespace()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+        };
+        let days = |selected: Selected| {
+            selected
+                .iter()
+                .map(|(d, source)| (d.day(), source.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            days(select(&args("3 1..=2 2"), None).unwrap()),
+            [(1, None), (2, None), (3, None)]
+        );
+        assert_eq!(
+            days(select(&args("7"), Some("-".to_string())).unwrap()),
+            [(7, Some("-".to_string()))]
+        );
+        assert!(select(&args("1..3"), Some("-".to_string())).is_err());
+        assert!(select(&args("99"), None).is_err());
+    }
+
+    struct Boom;
+
+    impl Solution for Boom {
+        const DAY: u32 = 99;
+
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &()) -> Result<u32> {
+            Ok(1)
+        }
+
+        fn part2(&self, _: &()) -> Result<u32> {
+            panic!("boom")
+        }
+    }
+
+    #[test]
+    fn solve_catches_panics() {
+        let (part1, part2) = Boom.solve("").unwrap();
+        assert_eq!(part1.unwrap(), "1");
+        assert_eq!(part2.unwrap_err().to_string(), "panicked: boom");
+    }
+}
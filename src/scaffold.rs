@@ -0,0 +1,238 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use regex::Regex;
+
+use crate::examples;
+
+pub const USAGE: &str = "usage: aoc new <day>";
+
+const DAY_TEMPLATE: &str = include_str!("day_template.rs.txt");
+
+const BIN_TEMPLATE: &str = "use std::process::ExitCode;
+
+use aoc::{cli, day{{NN}}::Day{{NN}}};
+
+fn main() -> ExitCode {
+    cli::main(&Day{{NN}})
+}
+";
+
+fn render(template: &str, day: u32) -> String {
+    template
+        .replace("{{NN}}", &format!("{:02}", day))
+        .replace("{{N}}", &day.to_string())
+}
+
+/// Add `line` among the lines of `text` matching `pattern`, whose first
+/// group is a day number, keeping them in day order. The new line copies the
+/// indentation of its neighbours.
+fn insert_line(text: &str, pattern: &str, day: u32, line: &str) -> Result<String, String> {
+    let re = Regex::new(pattern).unwrap();
+    let lines = text.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| re.captures(l).map(|c| (i, c[1].parse::<u32>().unwrap())))
+        .collect::<Vec<_>>();
+    let (last, _) = days
+        .last()
+        .ok_or_else(|| format!("no line matches `{}`", pattern))?;
+    if days.iter().any(|(_, d)| *d == day) {
+        return Err(format!("day {} is already registered", day));
+    }
+    let at = days
+        .iter()
+        .find(|(_, d)| *d > day)
+        .map(|(i, _)| *i)
+        .unwrap_or(last + 1);
+    let neighbour = lines[at.min(*last)];
+    let indent = &neighbour[..neighbour.len() - neighbour.trim_start().len()];
+
+    let mut lines = lines.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    lines.insert(at, format!("{}{}", indent, line));
+    let mut out = lines.join("\n");
+    if text.ends_with('\n') {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Declare the day's module in `lib.rs`.
+pub fn register_module(lib: &str, day: u32) -> Result<String, String> {
+    insert_line(
+        lib,
+        r"^pub mod day(\d+);$",
+        day,
+        &format!("pub mod day{:02};", day),
+    )
+}
+
+/// Import the day in `runner.rs` and add it to the registry.
+pub fn register_runner(runner: &str, day: u32) -> Result<String, String> {
+    let runner = insert_line(
+        runner,
+        r"^\s*day(\d+)::Day\d+,$",
+        day,
+        &format!("day{:02}::Day{:02},", day, day),
+    )?;
+    insert_line(
+        &runner,
+        r"^\s*Box::new\(Day(\d+)\b.*\),$",
+        day,
+        &format!("Box::new(Day{:02}),", day),
+    )
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Create a new day in the crate at `root`: its module and binary from the
+/// templates, empty input and example files, an entry in the examples and
+/// its place in the registry. Returns the files created.
+pub fn scaffold(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let module = root.join(format!("src/day{:02}.rs", day));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+    // Work out every edit before making any of them
+    let lib_path = root.join("src/lib.rs");
+    let lib = register_module(&read(&lib_path)?, day)
+        .map_err(|e| format!("{}: {}", lib_path.display(), e))?;
+    let runner_path = root.join("src/runner.rs");
+    let runner = register_runner(&read(&runner_path)?, day)
+        .map_err(|e| format!("{}: {}", runner_path.display(), e))?;
+    let examples_path = root.join(examples::PATH);
+    let examples = read(&examples_path)?;
+    let table = format!("[day{:02}.test]", day);
+
+    write(&lib_path, &lib)?;
+    write(&runner_path, &runner)?;
+    if !examples.contains(&table) {
+        write(
+            &examples_path,
+            &format!(
+                "{}\n\n{}\n# part1 =\n# part2 =\n",
+                examples.trim_end(),
+                table
+            ),
+        )?;
+    }
+    let mut created = Vec::new();
+    for (path, contents) in [
+        (module, render(DAY_TEMPLATE, day)),
+        (
+            root.join(format!("src/bin/day{:02}.rs", day)),
+            render(BIN_TEMPLATE, day),
+        ),
+        (root.join(format!("data/Day{:02}.txt", day)), String::new()),
+        (
+            root.join(format!("data/Day{:02}_test.txt", day)),
+            String::new(),
+        ),
+    ] {
+        if !path.exists() {
+            write(&path, &contents)?;
+            created.push(path);
+        }
+    }
+    Ok(created)
+}
+
+/// The `aoc new` command.
+pub fn main(args: &[String]) -> ExitCode {
+    let day = match args {
+        [day] => day.parse::<u32>().ok().filter(|d| (1..=25).contains(d)),
+        _ => None,
+    };
+    let day = match day {
+        Some(day) => day,
+        None => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match scaffold(Path::new("."), day) {
+        Ok(created) => {
+            for path in created {
+                println!("created {}", path.display());
+            }
+            println!("registered day {} in src/lib.rs and src/runner.rs", day);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_module_works() {
+        let lib = "pub mod input;\n\npub mod day01;\npub mod day03;\n";
+        assert_eq!(
+            register_module(lib, 2).unwrap(),
+            "pub mod input;\n\npub mod day01;\npub mod day02;\npub mod day03;\n"
+        );
+        assert_eq!(
+            register_module(lib, 21).unwrap(),
+            "pub mod input;\n\npub mod day01;\npub mod day03;\npub mod day21;\n"
+        );
+        assert!(register_module(lib, 3).is_err());
+    }
+
+    #[test]
+    fn register_runner_works() {
+        let runner = "use crate::{
+    day14::Day14,
+    day15::Day15,
+    error::Result,
+};
+
+pub fn days() -> Vec<Box<dyn Runnable>> {
+    vec![
+        Box::new(Day14),
+        Box::new(Day15::default()),
+    ]
+}
+";
+        assert_eq!(
+            register_runner(runner, 21).unwrap(),
+            "use crate::{
+    day14::Day14,
+    day15::Day15,
+    day21::Day21,
+    error::Result,
+};
+
+pub fn days() -> Vec<Box<dyn Runnable>> {
+    vec![
+        Box::new(Day14),
+        Box::new(Day15::default()),
+        Box::new(Day21),
+    ]
+}
+"
+        );
+    }
+
+    #[test]
+    fn render_works() {
+        let module = render(DAY_TEMPLATE, 7);
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("const DAY: u32 = 7;"));
+        assert!(!module.contains("{{"));
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use priority_queue::PriorityQueue;
+
+/// What a step can cost. `Default` is taken to be zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// The result of a successful search: the cost of the cheapest way to a goal,
+/// the nodes along it from the start to the goal, and every node the search
+/// looked at on the way, in the order it did.
+#[derive(Debug, Clone)]
+pub struct Route<N, C> {
+    pub cost: C,
+    pub path: Vec<N>,
+    pub visited: Vec<N>,
+}
+
+/// Everything a search reached from its starts, with the cost of the
+/// cheapest way to each node and where that way came from.
+#[derive(Debug, Clone)]
+pub struct Reached<N, C> {
+    costs: HashMap<N, C>,
+    came_from: HashMap<N, N>,
+}
+
+impl<N: Hash + Eq + Clone, C: Cost> Reached<N, C> {
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// Every node reached, with its cost.
+    pub fn costs(&self) -> impl Iterator<Item = (&N, C)> {
+        self.costs.iter().map(|(n, &c)| (n, c))
+    }
+
+    /// The cheapest way from a start to `node`, if it was reached.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.costs
+            .contains_key(node)
+            .then(|| path_to(&self.came_from, node.clone()))
+    }
+}
+
+/// Follow `came_from` back from `node` to the start it was reached from.
+fn path_to<N: Hash + Eq + Clone>(came_from: &HashMap<N, N>, node: N) -> Vec<N> {
+    let mut path = vec![node];
+    while let Some(previous) = came_from.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search from any of `starts` for a node that `is_goal`
+/// accepts, where every step costs one.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, usize>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut came_from = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut visited = Vec::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, cost)) = queue.pop_front() {
+        visited.push(node.clone());
+        if is_goal(&node) {
+            let path = path_to(&came_from, node);
+            return Some(Route {
+                cost,
+                path,
+                visited,
+            });
+        }
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                came_from.insert(next.clone(), node.clone());
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's search from any of `starts` for a node that `is_goal` accepts.
+/// `neighbours` gives each node's neighbours with the cost of the step to
+/// them.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, C>>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search from any of `starts` for a node that `is_goal` accepts.
+/// `neighbours` gives each node's neighbours with the cost of the step to
+/// them, and `heuristic` estimates the cost from a node to the nearest goal.
+/// The route found is the cheapest as long as the estimate never exceeds the
+/// real cost.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, C>>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best: HashMap<N, C> = HashMap::new();
+    let mut came_from = HashMap::new();
+    let mut visited = Vec::new();
+    let mut queue = PriorityQueue::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        best.insert(start.clone(), C::default());
+        queue.push(start, Reverse(estimate));
+    }
+    while let Some((node, _)) = queue.pop() {
+        let cost = best[&node];
+        visited.push(node.clone());
+        if is_goal(&node) {
+            let path = path_to(&came_from, node);
+            return Some(Route {
+                cost,
+                path,
+                visited,
+            });
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|&c| next_cost < c) {
+                let estimate = next_cost + heuristic(&next);
+                best.insert(next.clone(), next_cost);
+                came_from.insert(next.clone(), node.clone());
+                queue.push(next, Reverse(estimate));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's search from any of `starts` to everywhere they lead, rather
+/// than to a goal.
+pub fn explore<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Reached<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::new();
+    let mut came_from = HashMap::new();
+    let mut queue = PriorityQueue::new();
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        queue.push(start, Reverse(C::default()));
+    }
+    while let Some((node, Reverse(cost))) = queue.pop() {
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next.clone(), next_cost);
+                came_from.insert(next.clone(), node.clone());
+                queue.push(next, Reverse(next_cost));
+            }
+        }
+    }
+    Reached { costs, came_from }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -1-> b -1-> d, a -5-> d, a -1-> c -1-> b
+    fn graph(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 1), ('d', 5)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('b', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_works() {
+        let route = bfs(
+            ['a'],
+            |n| graph(n).into_iter().map(|(n, _)| n),
+            |&n| n == 'd',
+        )
+        .unwrap();
+        assert_eq!((route.cost, route.path), (1, vec!['a', 'd']));
+        assert!(bfs(
+            ['b'],
+            |n| graph(n).into_iter().map(|(n, _)| n),
+            |&n| n == 'a'
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn dijkstra_works() {
+        let route = dijkstra(['a'], graph, |&n| n == 'd').unwrap();
+        assert_eq!((route.cost, route.path), (2, vec!['a', 'b', 'd']));
+        assert!(route.visited.contains(&'c'));
+        let route = dijkstra(['c', 'a'], graph, |&n| n == 'b').unwrap();
+        assert_eq!((route.cost, route.path.len()), (1, 2));
+        assert!(dijkstra(['d'], graph, |&n| n == 'a').is_none());
+    }
+
+    #[test]
+    fn explore_works() {
+        let reached = explore(['a'], graph);
+        assert_eq!(reached.cost(&'d'), Some(2));
+        assert_eq!(reached.cost(&'c'), Some(1));
+        assert_eq!(reached.path_to(&'d'), Some(vec!['a', 'b', 'd']));
+        assert_eq!(explore(['b'], graph).path_to(&'a'), None);
+        assert_eq!(explore(['b'], graph).costs().count(), 2);
+    }
+
+    #[test]
+    fn astar_works() {
+        let goal = (3, 2);
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 3 && y <= 2)
+                .map(|p| (p, 1))
+        };
+        let heuristic = |&(x, y): &(i32, i32)| (goal.0 - x) + (goal.1 - y);
+        let route = astar([(0, 0)], neighbours, heuristic, |&p| p == goal).unwrap();
+        assert_eq!(route.cost, 5);
+        assert_eq!(route.path.first(), Some(&(0, 0)));
+        assert_eq!(route.path.last(), Some(&goal));
+        assert_eq!(route.path.len(), 6);
+    }
+}
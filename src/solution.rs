@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use crate::error::Result;
+
+/// One day's puzzle. `parse` turns the raw input into whatever shape both
+/// parts want, so the runner, benchmarks and tests can drive every day the
+/// same way.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// The solver to use on the puzzle's examples, for days whose examples
+    /// are sized differently from the real input.
+    fn for_examples(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+fn print_answer(part: u32, answer: impl Display) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+/// What each day's binary does: parse the input once and print both parts.
+pub fn print_answers<S: Solution>(solution: &S, input: &str) -> Result<()> {
+    let parsed = solution.parse(input)?;
+    print_answer(1, solution.part1(&parsed)?);
+    print_answer(2, solution.part2(&parsed)?);
+    Ok(())
+}
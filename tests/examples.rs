@@ -0,0 +1,31 @@
+//! Every example in `data/examples.toml`, run through its day.
+
+use aoc::{
+    answers::{self, Verdict},
+    cache::Cache,
+    examples,
+};
+
+#[test]
+fn examples_work() {
+    let cache = Cache::default();
+    let failures = examples::load(examples::PATH)
+        .unwrap()
+        .iter()
+        .flat_map(|example| {
+            (1..)
+                .zip(examples::check(example, &cache))
+                .filter(|(_, v)| !matches!(v, Verdict::Pass | Verdict::Missing))
+                .map(move |(part, v)| {
+                    format!(
+                        "day {} {} part {}: {}",
+                        example.day,
+                        example.name,
+                        part,
+                        answers::describe(&v)
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}